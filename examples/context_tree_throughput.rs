@@ -0,0 +1,72 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Measures how many bits per second a context tree processes in the
+// update/revert pattern of Monte-Carlo search. Run with:
+//
+//   cargo run --release --example context_tree_throughput
+
+extern crate ai;
+
+use ai::bitstring::Bitstring;
+use ai::predictor::context_tree::ContextTree;
+use ai::predictor::estimator::EstimatorType;
+use ai::predictor::Predictor;
+use ai::random::{Random, RandomImpl};
+
+use std::time::Instant;
+
+const DEPTH: usize = 48;
+const HISTORY_BITS: usize = 20000;
+const SIMULATION_BITS: usize = 64;
+const NUM_SIMULATIONS: usize = 20000;
+
+fn main() {
+  let mut random = RandomImpl::create(5761567);
+  let mut tree = ContextTree::create(DEPTH, EstimatorType::KrichevskyTrofimov);
+  tree.update(&random_bits(&mut random, HISTORY_BITS));
+
+  // Draw all bits up front so that only the tree is timed.
+  let simulations: Vec<Bitstring> = (0..NUM_SIMULATIONS)
+      .map(|_| random_bits(&mut random, SIMULATION_BITS))
+      .collect();
+
+  let start = Instant::now();
+  for bits in simulations.iter() {
+    let size = tree.history_size();
+    tree.update(bits);
+    tree.revert_to_history_size(size);
+  }
+  let seconds = start.elapsed().as_secs_f64();
+
+  let num_bits = (NUM_SIMULATIONS * SIMULATION_BITS) as f64;
+  println!("Depth {} tree with a {} bit history: {:.0} bits/s",
+      DEPTH, HISTORY_BITS, num_bits / seconds);
+}
+
+fn random_bits(random: &mut Random, num_bits: usize) -> Bitstring {
+  let mut result = Bitstring::new();
+  for _ in 0..num_bits {
+    result.push_u64(random.next_modulo(2), 1);
+  }
+  return result;
+}
@@ -20,17 +20,71 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use explorer::{ExplorerFactory, ExplorerFactoryImpl};
-use predictor::context_tree::ContextTree;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use encoding::Encoder;
+use explorer::{ExplorationSchedule, ExplorerFactory, ExplorerFactoryImpl};
+use explorer::{ExplorerType, MonteCarloParams};
+use predictor;
 use predictor::Predictor;
+use random::RandomImpl;
+use serialization;
 use types::{Action, Observation, Reward};
 
+/// Identifies the serialized format of an agent checkpoint.
+const MAGIC: &[u8; 4] = b"AGNT";
+const VERSION: u32 = 1;
+
+/// The range of percepts an environment declares it can produce.
+#[derive(Copy, Clone, Debug)]
+pub struct PerceptBounds {
+  pub min_observation: Observation,
+  pub max_observation: Observation,
+  pub min_reward: Reward,
+  pub max_reward: Reward,
+}
+
+impl PerceptBounds {
+  pub fn contains(&self, observation: Observation, reward: Reward) -> bool {
+    let (Observation(o), Reward(r)) = (observation, reward);
+    let (Observation(min_o), Observation(max_o)) =
+        (self.min_observation, self.max_observation);
+    let (Reward(min_r), Reward(max_r)) = (self.min_reward, self.max_reward);
+    return o >= min_o && o <= max_o && r >= min_r && r <= max_r;
+  }
+
+  pub fn save(&self, writer: &mut Write) -> io::Result<()> {
+    let Observation(min_observation) = self.min_observation;
+    let Observation(max_observation) = self.max_observation;
+    let Reward(min_reward) = self.min_reward;
+    let Reward(max_reward) = self.max_reward;
+    serialization::write_u32(writer, min_observation as u32)?;
+    serialization::write_u32(writer, max_observation as u32)?;
+    serialization::write_f64(writer, min_reward)?;
+    serialization::write_f64(writer, max_reward)
+  }
+
+  pub fn load(reader: &mut Read) -> io::Result<PerceptBounds> {
+    Ok(PerceptBounds {
+      min_observation: Observation(serialization::read_u32(reader)? as i32),
+      max_observation: Observation(serialization::read_u32(reader)? as i32),
+      min_reward: Reward(serialization::read_f64(reader)?),
+      max_reward: Reward(serialization::read_f64(reader)?),
+    })
+  }
+}
+
 /// Model struct for an agent which can interact with an environment.
 pub struct Agent {
   age: i32,
   total_reward: Reward,
   num_actions: i16,
 
+  /// Defines how actions and percepts are presented to the predictor.
+  encoder: Encoder,
+
   /// This agent's model of the environment. Used to predict
   /// (observation, reward) pairs in order to decide how to act.
   predictor: Box<Predictor>,
@@ -38,33 +92,143 @@ pub struct Agent {
   /// A factory used to create an explorer whenever this agents needs to
   /// decide what its next action will be.
   explorer_factory: Box<ExplorerFactory>,
+  explorer_type: ExplorerType,
+
+  /// If present, decides when to pick a random action instead of using the
+  /// explorer.
+  exploration_schedule: Option<ExplorationSchedule>,
+
+  /// If present, every percept must lie within these bounds.
+  percept_bounds: Option<PerceptBounds>,
 }
 
 impl Agent {
   pub fn create_aixi(
       num_actions: i16,
-      context_tree_depth: usize) -> Self {
-    Agent::new(
+      encoder: Encoder,
+      predictor: Box<Predictor>,
+      explorer_type: ExplorerType,
+      monte_carlo_params: MonteCarloParams,
+      random: RandomImpl) -> Self {
+    let mut agent = Agent::new(
         num_actions,
-        Box::new(ContextTree::create(context_tree_depth)),
-        Box::new(ExplorerFactoryImpl::new()))
+        encoder,
+        predictor,
+        Box::new(ExplorerFactoryImpl::new(
+            random, encoder, monte_carlo_params)));
+    agent.explorer_type = explorer_type;
+    return agent;
   }
 
   /// Visible for testing.
   pub fn new(
       num_actions: i16,
+      encoder: Encoder,
       predictor: Box<Predictor>,
       explorer_factory: Box<ExplorerFactory>) -> Self {
     Agent {
       age: 0,
       total_reward: Reward(0.0),
       num_actions: num_actions,
+      encoder: encoder,
       predictor: predictor,
       explorer_factory: explorer_factory,
+      explorer_type: ExplorerType::MonteCarlo,
+      exploration_schedule: None,
+      percept_bounds: None,
     }
   }
 
+  /// Makes the agent occasionally pick random actions, as decided by the
+  /// supplied schedule.
+  pub fn set_exploration_schedule(&mut self, schedule: ExplorationSchedule) {
+    self.exploration_schedule = Some(schedule);
+  }
+
+  /// Makes the agent reject percepts outside of the supplied bounds, see
+  /// update. The bounds are part of checkpoints.
+  pub fn set_percept_bounds(&mut self, bounds: PerceptBounds) {
+    self.percept_bounds = Some(bounds);
+  }
+
+  /// Writes a checkpoint of this agent, including its predictor and the
+  /// state of its random number generators. An agent restored using load
+  /// behaves exactly like this agent would have.
+  pub fn save(&self, writer: &mut Write) -> io::Result<()> {
+    serialization::write_header(writer, MAGIC, VERSION)?;
+    serialization::write_u32(writer, self.age as u32)?;
+    let Reward(total_reward) = self.total_reward;
+    serialization::write_f64(writer, total_reward)?;
+    serialization::write_u32(writer, self.num_actions as u32)?;
+    self.encoder.save(writer)?;
+    self.predictor.save(writer)?;
+    self.explorer_factory.save(writer)?;
+    serialization::write_u8(writer, match self.explorer_type {
+      ExplorerType::MonteCarlo => 0,
+      ExplorerType::Random => 1,
+    })?;
+    match self.exploration_schedule {
+      Some(ref schedule) => {
+        serialization::write_u8(writer, 1)?;
+        schedule.save(writer)?;
+      },
+      None => serialization::write_u8(writer, 0)?,
+    }
+    match self.percept_bounds {
+      Some(ref bounds) => {
+        serialization::write_u8(writer, 1)?;
+        bounds.save(writer)
+      },
+      None => serialization::write_u8(writer, 0),
+    }
+  }
+
+  /// Restores an agent from a checkpoint written by save(). The explorer
+  /// factory is always restored as an ExplorerFactoryImpl.
+  pub fn load(reader: &mut Read) -> io::Result<Agent> {
+    serialization::read_header(reader, MAGIC, VERSION)?;
+    let age = serialization::read_u32(reader)? as i32;
+    let total_reward = Reward(serialization::read_f64(reader)?);
+    let num_actions = serialization::read_u32(reader)? as i16;
+    let encoder = Encoder::load(reader)?;
+    let predictor = predictor::load(reader)?;
+    let explorer_factory = ExplorerFactoryImpl::load(reader)?;
+    let explorer_type = serialization::read_u8(reader)?;
+
+    let mut agent = Agent::new(
+        num_actions, encoder, predictor, Box::new(explorer_factory));
+    agent.age = age;
+    agent.total_reward = total_reward;
+    agent.explorer_type = match explorer_type {
+      0 => ExplorerType::MonteCarlo,
+      1 => ExplorerType::Random,
+      _ => return Err(serialization::invalid_data("Unknown explorer type")),
+    };
+    if serialization::read_u8(reader)? != 0 {
+      agent.exploration_schedule = Some(ExplorationSchedule::load(reader)?);
+    }
+    if serialization::read_u8(reader)? != 0 {
+      agent.percept_bounds = Some(PerceptBounds::load(reader)?);
+    }
+    return Ok(agent);
+  }
+
+  /// Writes a checkpoint to a file, replacing any existing contents.
+  pub fn save_to_file(&self, path: &Path) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    self.save(&mut writer)?;
+    writer.flush()
+  }
+
+  /// Restores an agent from a file previously written by save_to_file().
+  pub fn load_from_file(path: &Path) -> io::Result<Agent> {
+    let mut reader = BufReader::new(File::open(path)?);
+    Agent::load(&mut reader)
+  }
+
   pub fn age(&self) -> i32 { self.age }
+  pub fn num_actions(&self) -> i16 { self.num_actions }
+  pub fn encoder(&self) -> Encoder { self.encoder }
 
   /// Returns the total reward accumulated so far.
   pub fn total_reward(&self) -> Reward { self.total_reward }
@@ -74,23 +238,47 @@ impl Agent {
     return self.total_reward / (self.age as f64);
   }
 
-  /// Returns an action in [0, num_actions - 1].
+  /// Returns an action in [0, num_actions - 1]. The action is added to the
+  /// history of the predictor.
   pub fn act(&mut self) -> Action {
-    let mut mc_explorer = self.explorer_factory.create_monte_carlo_explorer(
-        &mut *self.predictor);
-    mc_explorer.explore(self.num_actions);
-    // TODO(dinowernli): Return this result. For now, use the random explorer.
+    let explore = match self.exploration_schedule {
+      Some(ref mut schedule) => schedule.should_explore(),
+      None => false,
+    };
+    let explorer_type =
+        if explore { ExplorerType::Random } else { self.explorer_type };
+
+    let action = {
+      let mut explorer = match explorer_type {
+        ExplorerType::MonteCarlo =>
+            self.explorer_factory.create_monte_carlo_explorer(
+                &mut *self.predictor),
+        ExplorerType::Random => self.explorer_factory.create_random_explorer(),
+      };
+      explorer.explore(self.num_actions)
+    };
 
-    let mut random_explorer = self.explorer_factory.create_random_explorer();
-    return random_explorer.explore(self.num_actions);
+    self.predictor.update_history(&self.encoder.encode_action(action));
+    return action;
   }
 
   /// Update the agent's view of the world based on a new
-  /// (observation, reward) pair.
-  pub fn update(&mut self, observation: Observation, reward: Reward) {
-    // TODO(dinowernli): Inform the predictor of the new data.
-    #![allow(unused_variables)]
+  /// (observation, reward) pair. Returns an error and leaves the agent
+  /// unchanged if the percept is outside of the agent's percept bounds.
+  pub fn update(
+      &mut self, observation: Observation, reward: Reward)
+      -> Result<(), String> {
+    if let Some(ref bounds) = self.percept_bounds {
+      if !bounds.contains(observation, reward) {
+        return Err(format!("Percept ({:?}, {:?}) is outside of {:?}",
+            observation, reward, bounds));
+      }
+    }
+    let percept = self.encoder.encode_percept(observation, reward);
+    self.predictor.update(&percept);
+
     self.age = self.age + 1;
     self.total_reward = self.total_reward + reward;
+    return Ok(());
   }
 }
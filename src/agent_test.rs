@@ -20,50 +20,218 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use agent::Agent;
+use std::cell::RefCell;
+use std::io::Cursor;
+use std::rc::Rc;
+
+use agent::{Agent, PerceptBounds};
 use bitstring::Bitstring;
-use explorer::{Explorer, ExplorerFactory};
+use encoding::Encoder;
+use explorer::{ExplorationSchedule, Explorer, ExplorerFactory, ExplorerType};
+use explorer::MonteCarloParams;
+use predictor::context_tree::ContextTree;
+use predictor::estimator::EstimatorType;
 use predictor::Predictor;
+use random::RandomImpl;
 use types::{Action, Observation, Reward};
 
 #[test]
 fn age() {
-  let fake_predictor = Box::new(FakePredictor);
+  let fake_predictor = Box::new(FakePredictor::new());
   let fake_explorer_factory = Box::new(FakeExplorerFactory);
-  let mut agent = Agent::new(10, fake_predictor, fake_explorer_factory);
+  let encoder = Encoder::new(4, 3, 3, 0.0);
+  let mut agent =
+      Agent::new(10, encoder, fake_predictor, fake_explorer_factory);
 
   assert_eq!(0, agent.age());
-  agent.update(Observation(3), Reward(4.0));
+  agent.update(Observation(3), Reward(4.0)).unwrap();
   assert_eq!(1, agent.age());
 }
 
 #[test]
 fn reward() {
-  let fake_predictor = Box::new(FakePredictor);
+  let fake_predictor = Box::new(FakePredictor::new());
   let fake_explorer_factory = Box::new(FakeExplorerFactory);
-  let mut agent = Agent::new(10, fake_predictor, fake_explorer_factory);
+  let encoder = Encoder::new(4, 3, 3, 0.0);
+  let mut agent =
+      Agent::new(10, encoder, fake_predictor, fake_explorer_factory);
 
   assert_eq!(Reward(0.0), agent.total_reward());
-  agent.update(Observation(3), Reward(4.0));
+  agent.update(Observation(3), Reward(4.0)).unwrap();
   assert_eq!(Reward(4.0), agent.total_reward());
 }
 
+#[test]
+fn updates_predictor() {
+  let fake_predictor = Box::new(FakePredictor::new());
+  let history = fake_predictor.history.clone();
+  let fake_explorer_factory = Box::new(FakeExplorerFactory);
+  let encoder = Encoder::new(2, 2, 3, 0.0);
+  let mut agent = Agent::new(4, encoder, fake_predictor, fake_explorer_factory);
+
+  // The fake explorer always picks action 0, which takes 2 bits.
+  agent.act();
+  assert_eq!("00", *history.borrow());
+
+  // Observation 3 takes 2 bits and reward 5 takes 3 bits.
+  agent.update(Observation(3), Reward(5.0)).unwrap();
+  assert_eq!("0011101", *history.borrow());
+}
+
+#[test]
+fn percept_bounds() {
+  let bounds = PerceptBounds {
+    min_observation: Observation(1),
+    max_observation: Observation(3),
+    min_reward: Reward(-1.0),
+    max_reward: Reward(2.0),
+  };
+  assert!(bounds.contains(Observation(1), Reward(-1.0)));
+  assert!(bounds.contains(Observation(3), Reward(2.0)));
+  assert!(!bounds.contains(Observation(0), Reward(0.0)));
+  assert!(!bounds.contains(Observation(4), Reward(0.0)));
+  assert!(!bounds.contains(Observation(2), Reward(-2.0)));
+  assert!(!bounds.contains(Observation(2), Reward(3.0)));
+}
+
+#[test]
+fn rejects_percepts_out_of_bounds() {
+  let fake_predictor = Box::new(FakePredictor::new());
+  let fake_explorer_factory = Box::new(FakeExplorerFactory);
+  let encoder = Encoder::new(4, 3, 3, 0.0);
+  let mut agent =
+      Agent::new(10, encoder, fake_predictor, fake_explorer_factory);
+  agent.set_percept_bounds(PerceptBounds {
+    min_observation: Observation(0),
+    max_observation: Observation(3),
+    min_reward: Reward(0.0),
+    max_reward: Reward(4.0),
+  });
 
-// Fake predictor.
+  agent.update(Observation(3), Reward(4.0)).unwrap();
+  let result = agent.update(Observation(4), Reward(4.0));
+  assert!(result.unwrap_err().contains("is outside of"));
 
-struct FakePredictor;
+  // Rejected percepts are not part of the history.
+  assert_eq!(1, agent.age());
+  assert_eq!(Reward(4.0), agent.total_reward());
+}
+
+#[test]
+fn save_unserializable() {
+  // The fakes do not support checkpoints, which is reported as an error.
+  let fake_predictor = Box::new(FakePredictor::new());
+  let fake_explorer_factory = Box::new(FakeExplorerFactory);
+  let encoder = Encoder::new(4, 3, 3, 0.0);
+  let agent = Agent::new(10, encoder, fake_predictor, fake_explorer_factory);
+
+  let mut bytes = Vec::new();
+  assert!(agent.save(&mut bytes).is_err());
+}
+
+#[test]
+fn save_and_load() {
+  let params = MonteCarloParams {
+    horizon: 2,
+    num_simulations: 20,
+    exploration_constant: 1.0,
+    reward_range: None,
+  };
+  let predictor = ContextTree::create(3, EstimatorType::KrichevskyTrofimov);
+  let mut agent = Agent::create_aixi(
+      3, Encoder::new(2, 2, 2, 0.0), Box::new(predictor),
+      ExplorerType::MonteCarlo, params, RandomImpl::create(1234));
+  agent.set_exploration_schedule(
+      ExplorationSchedule::new(0.5, 0.9, RandomImpl::create(99)));
+  agent.set_percept_bounds(PerceptBounds {
+    min_observation: Observation(0),
+    max_observation: Observation(3),
+    min_reward: Reward(0.0),
+    max_reward: Reward(3.0),
+  });
+  for i in 0..5 {
+    let Action(action) = agent.act();
+    agent.update(Observation(i % 4), Reward((action % 4) as f64)).unwrap();
+  }
+
+  let mut bytes = Vec::new();
+  agent.save(&mut bytes).unwrap();
+  let mut loaded = Agent::load(&mut Cursor::new(bytes)).unwrap();
+  assert_eq!(agent.age(), loaded.age());
+  assert_eq!(agent.total_reward(), loaded.total_reward());
+
+  // Both agents share all state, including their random number generators,
+  // so they keep making the same decisions.
+  for i in 0..5 {
+    let Action(expected) = agent.act();
+    let Action(actual) = loaded.act();
+    assert_eq!(expected, actual);
+
+    let observation = Observation(i % 4);
+    agent.update(observation, Reward((expected % 4) as f64)).unwrap();
+    loaded.update(observation, Reward((actual % 4) as f64)).unwrap();
+  }
+  assert_eq!(agent.total_reward(), loaded.total_reward());
+
+  // The percept bounds are restored as well.
+  assert!(loaded.update(Observation(4), Reward(0.0)).is_err());
+}
+
+
+#[test]
+fn load_rejects_invalid_data() {
+  let params = MonteCarloParams {
+    horizon: 2,
+    num_simulations: 20,
+    exploration_constant: 1.0,
+    reward_range: None,
+  };
+  let predictor = ContextTree::create(3, EstimatorType::KrichevskyTrofimov);
+  let agent = Agent::create_aixi(
+      3, Encoder::new(2, 2, 2, 0.0), Box::new(predictor),
+      ExplorerType::MonteCarlo, params, RandomImpl::create(1234));
+  let mut bytes = Vec::new();
+  agent.save(&mut bytes).unwrap();
+  assert!(Agent::load(&mut Cursor::new(bytes.clone())).is_ok());
+
+  // Missing the exploration schedule flag.
+  let mut truncated = bytes.clone();
+  truncated.pop();
+  assert!(Agent::load(&mut Cursor::new(truncated)).is_err());
+
+  // The version directly follows the magic.
+  let mut unknown_version = bytes.clone();
+  unknown_version[4] = 2;
+  assert!(Agent::load(&mut Cursor::new(unknown_version)).is_err());
+}
+
+// Fake predictor which records its history in a shared string.
+
+struct FakePredictor {
+  history: Rc<RefCell<String>>,
+}
+
+impl FakePredictor {
+  fn new() -> Self {
+    FakePredictor {
+      history: Rc::new(RefCell::new(String::new())),
+    }
+  }
+}
 
 impl Predictor for FakePredictor {
-  fn history_size(&self) -> usize { 17 }
+  fn history_size(&self) -> usize { self.history.borrow().len() }
 
   fn revert_to_history_size(&mut self, target_size: usize) {
-    #![allow(unused_variables)]
-    // Do nothing.
+    self.history.borrow_mut().truncate(target_size);
   }
 
   fn update(&mut self, bits: &Bitstring) {
-    #![allow(unused_variables)]
-    // Do nothing.
+    self.history.borrow_mut().push_str(&bits.to_string());
+  }
+
+  fn update_history(&mut self, bits: &Bitstring) {
+    self.update(bits);
   }
 
   fn predict(&mut self, bits: &Bitstring) -> f64 {
@@ -79,12 +247,12 @@ struct FakeExplorerFactory;
 
 impl ExplorerFactory for FakeExplorerFactory {
   fn create_monte_carlo_explorer(
-      &self, predictor: &mut Predictor) -> Box<Explorer> {
+      &mut self, predictor: &mut Predictor) -> Box<Explorer> {
     #![allow(unused_variables)]
     Box::new(FakeExplorer)
   }
 
-  fn create_random_explorer(&self) -> Box<Explorer> {
+  fn create_random_explorer(&mut self) -> Box<Explorer> {
     Box::new(FakeExplorer)
   }
 }
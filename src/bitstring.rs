@@ -20,27 +20,41 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use std::string::String;
-use std::string::ToString;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::{Add, Index};
+use std::str::FromStr;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Bit {
   Zero,
   One,
 }
 
-/// Basic representation of a sequence of bits.
+/// Allows Index to hand out references to bits.
+static ZERO: Bit = Bit::Zero;
+static ONE: Bit = Bit::One;
+
+/// The number of bits stored in each word.
+const WORD_BITS: usize = 64;
+
+/// Basic representation of a sequence of bits. The bits are packed into
+/// words, most significant bit first. Bits past the end of the sequence are
+/// always zero, which keeps equality and hashing on the words valid.
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Bitstring {
-  // TODO(dinowernli): Investigate storing a Vec<u64> instead
-  // and doing the conversoin to {Zero, One} upon request.
-  bits: Vec<Bit>,
+  words: Vec<u64>,
+  len: usize,
 }
 
 /// A type representing a sequence of Bits.
 impl Bitstring {
   pub fn new() -> Self {
     return Bitstring {
-      bits: Vec::new(),
+      words: Vec::new(),
+      len: 0,
     };
   }
 
@@ -53,72 +67,410 @@ impl Bitstring {
   /// smallest sequence of bits which can represent the value, i.e.,
   /// log2(ceil(value)).
   pub fn create_from_u64(value: u64) -> Self {
-    if value == 0 {
-      return Bitstring::create_from_bits(vec!(Bit::Zero));
-    }
-
-    // We compute the bit values by repeatedly getting the least
-    // significant bit and then shifting. Since pushing to the end of
-    // a vector is more efficient than pushing to the start, we keep
-    // appending more significant bits and then flip everything once
-    // at the end.
-    let mut bits = Vec::new();
-    let mut remaining = value;
-    while remaining > 0 {
-      let least = remaining & 0x1;
-      bits.push(if least == 1 { Bit::One } else { Bit::Zero });
-      remaining = remaining >> 1;
-    }
+    let num_bits = if value == 0 {
+      1
+    } else {
+      WORD_BITS - value.leading_zeros() as usize
+    };
+    return Bitstring::create_from_u64_fixed(value, num_bits);
+  }
 
-    bits.reverse();
-    return Bitstring::create_from_bits(bits);
+  /// Encodes the supplied value using exactly num_bits bits, most
+  /// significant bit first. Panics if the value does not fit.
+  pub fn create_from_u64_fixed(value: u64, num_bits: usize) -> Self {
+    let mut result = Bitstring::new();
+    result.push_u64(value, num_bits);
+    return result;
   }
 
   /// Returns a bitstring created from a sequence of characters, each of which
   /// must be either '0' or '1'. Panics if a bad string is passed.
   pub fn create_from_string(value: &str) -> Self {
-    let mut bits = Vec::new();
-    for b in value.chars() {
-      bits.push(match b {
-        '0' => Bit::Zero,
-        '1' => Bit::One,
-        _ => panic!("Could not extract bitstring from {}", value),
-      });
+    match value.parse() {
+      Ok(result) => result,
+      Err(_) => panic!("Could not extract bitstring from {}", value),
     }
-    return Bitstring::create_from_bits(bits);
   }
 
-  fn create_from_bits(bits: Vec<Bit>) -> Self {
-    return Bitstring {
-      bits: bits
-    };
+  /// Returns a bitstring containing all bits of the supplied bytes, most
+  /// significant bit of the first byte first.
+  pub fn create_from_bytes(bytes: &[u8]) -> Self {
+    let mut result = Bitstring::new();
+    for byte in bytes {
+      result.push_u64(*byte as u64, 8);
+    }
+    return result;
   }
 
-  pub fn bits(&self) -> &Vec<Bit> { &self.bits }
-  pub fn len(&self) -> usize { self.bits().len() }
-  pub fn bit(&self, i: usize) -> Bit { self.bits[i] }
+  pub fn len(&self) -> usize { self.len }
+  pub fn is_empty(&self) -> bool { self.len == 0 }
+
+  pub fn bit(&self, i: usize) -> Bit {
+    assert!(i < self.len, "Index {} out of range for length {}", i, self.len);
+    let word = self.words[i / WORD_BITS];
+    if (word >> (WORD_BITS - 1 - i % WORD_BITS)) & 0x1 == 1 {
+      Bit::One
+    } else {
+      Bit::Zero
+    }
+  }
+
+  /// Returns an iterator over the bits, starting with the first bit.
+  pub fn iter<'a>(&'a self) -> Iter<'a> {
+    self.as_slice().iter()
+  }
+
+  /// Returns a view of the entire bitstring.
+  pub fn as_slice<'a>(&'a self) -> BitSlice<'a> {
+    self.slice(0, self.len)
+  }
+
+  /// Returns a view of the bits with indices in [start, end).
+  pub fn slice<'a>(&'a self, start: usize, end: usize) -> BitSlice<'a> {
+    assert!(start <= end && end <= self.len,
+        "Range [{}, {}) out of range for length {}", start, end, self.len);
+    BitSlice {
+      bitstring: self,
+      start: start,
+      len: end - start,
+    }
+  }
 
   pub fn push(&mut self, bit: Bit) {
-    self.bits.push(bit);
+    let offset = self.len % WORD_BITS;
+    if offset == 0 {
+      self.words.push(0);
+    }
+    if bit == Bit::One {
+      *self.words.last_mut().unwrap() |= 1 << (WORD_BITS - 1 - offset);
+    }
+    self.len = self.len + 1;
   }
 
   /// Only valid if this bit string is not empty.
   pub fn pop(&mut self) -> Bit {
-    return self.bits.pop().unwrap();
+    let bit = self.bit(self.len - 1);
+    self.truncate(self.len - 1);
+    return bit;
+  }
+
+  /// Shortens the bitstring to the supplied length. Does nothing if the
+  /// bitstring is not longer than that.
+  pub fn truncate(&mut self, len: usize) {
+    if len >= self.len {
+      return;
+    }
+    self.len = len;
+    self.words.truncate(len.div_ceil(WORD_BITS));
+
+    let offset = len % WORD_BITS;
+    if offset > 0 {
+      *self.words.last_mut().unwrap() &= !(u64::max_value() >> offset);
+    }
+  }
+
+  /// Appends the lowest num_bits bits of the supplied value, most significant
+  /// bit first. All other bits of the value must be zero.
+  pub fn push_u64(&mut self, value: u64, num_bits: usize) {
+    assert!(num_bits <= WORD_BITS);
+    assert!(num_bits == WORD_BITS || value >> num_bits == 0,
+        "Value {} does not fit into {} bits", value, num_bits);
+    if num_bits == 0 {
+      return;
+    }
+
+    let offset = self.len % WORD_BITS;
+    if offset == 0 {
+      self.words.push(0);
+    }
+
+    // Position the value in a window made up of the last word and the word
+    // after it, then split the window into its two halves.
+    let window = (value as u128) << (2 * WORD_BITS - offset - num_bits);
+    *self.words.last_mut().unwrap() |= (window >> WORD_BITS) as u64;
+    if offset + num_bits > WORD_BITS {
+      self.words.push(window as u64);
+    }
+    self.len = self.len + num_bits;
+  }
+
+  /// Appends all bits of the supplied bitstring.
+  pub fn append(&mut self, other: &Bitstring) {
+    self.append_slice(other.as_slice());
+  }
+
+  /// Appends all bits of the supplied slice.
+  pub fn append_slice(&mut self, slice: BitSlice) {
+    let mut start = 0;
+    while start < slice.len() {
+      let num_bits = WORD_BITS.min(slice.len() - start);
+      self.push_u64(slice.extract_u64(start, num_bits), num_bits);
+      start = start + num_bits;
+    }
+  }
+
+  /// Returns the unsigned integer represented by the num_bits bits starting
+  /// at the supplied index, where the first bit is the most significant.
+  pub fn extract_u64(&self, start: usize, num_bits: usize) -> u64 {
+    assert!(num_bits <= WORD_BITS);
+    assert!(start + num_bits <= self.len,
+        "Range [{}, {}) out of range for length {}",
+        start, start + num_bits, self.len);
+    if num_bits == 0 {
+      return 0;
+    }
+
+    // Read a window of two words such that the requested range is contained
+    // in it, then shift out everything else.
+    let index = start / WORD_BITS;
+    let high = self.words[index] as u128;
+    let low = *self.words.get(index + 1).unwrap_or(&0) as u128;
+    let window = (high << WORD_BITS) | low;
+    let offset = start % WORD_BITS;
+    return ((window << offset) >> (2 * WORD_BITS - num_bits)) as u64;
+  }
+
+  /// Returns the unsigned integer represented by all bits, where the first
+  /// bit is the most significant. Only valid for at most 64 bits.
+  pub fn to_u64(&self) -> u64 {
+    self.extract_u64(0, self.len)
+  }
+
+  /// Returns the bits packed into bytes, most significant bit first. The
+  /// last byte is padded with zeroes.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut result = Vec::with_capacity(self.len.div_ceil(8));
+    for word in self.words.iter() {
+      for i in 0..(WORD_BITS / 8) {
+        result.push((word >> (WORD_BITS - 8 * (i + 1))) as u8);
+      }
+    }
+    result.truncate(self.len.div_ceil(8));
+    return result;
   }
 }
 
-impl ToString for Bitstring {
-  /// Returns the string representation of the bitstring, i.e., the
+impl fmt::Display for Bitstring {
+  /// Formats the bitstring as a sequence of zeroes and ones, i.e., the
   /// string representation of 5 is '101'.
-  fn to_string(&self) -> String {
-    let mut result = String::new();
-    for bit in self.bits() {
-      result.push(match *bit {
-        Bit::Zero => '0',
-        Bit::One => '1',
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.as_slice().fmt(f)
+  }
+}
+
+impl fmt::Debug for Bitstring {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "Bitstring({})", self)
+  }
+}
+
+impl FromStr for Bitstring {
+  type Err = ParseBitstringError;
+
+  fn from_str(value: &str) -> Result<Bitstring, ParseBitstringError> {
+    let mut result = Bitstring::new();
+    for (position, character) in value.chars().enumerate() {
+      result.push(match character {
+        '0' => Bit::Zero,
+        '1' => Bit::One,
+        _ => return Err(ParseBitstringError {
+          character: character,
+          position: position,
+        }),
       });
     }
+    return Ok(result);
+  }
+}
+
+impl Index<usize> for Bitstring {
+  type Output = Bit;
+
+  fn index(&self, i: usize) -> &Bit {
+    match self.bit(i) {
+      Bit::Zero => &ZERO,
+      Bit::One => &ONE,
+    }
+  }
+}
+
+impl <'a> IntoIterator for &'a Bitstring {
+  type Item = Bit;
+  type IntoIter = Iter<'a>;
+
+  fn into_iter(self) -> Iter<'a> {
+    self.iter()
+  }
+}
+
+impl Extend<Bit> for Bitstring {
+  fn extend<T: IntoIterator<Item = Bit>>(&mut self, bits: T) {
+    for bit in bits {
+      self.push(bit);
+    }
+  }
+}
+
+impl FromIterator<Bit> for Bitstring {
+  fn from_iter<T: IntoIterator<Item = Bit>>(bits: T) -> Bitstring {
+    let mut result = Bitstring::new();
+    result.extend(bits);
     return result;
   }
 }
+
+impl <'a> Add<&'a Bitstring> for Bitstring {
+  type Output = Bitstring;
+
+  /// Concatenates the two bitstrings.
+  fn add(self, rhs: &'a Bitstring) -> Bitstring {
+    let mut result = self;
+    result.append(rhs);
+    return result;
+  }
+}
+
+/// A borrowed view of a contiguous range of bits in a bitstring.
+#[derive(Copy, Clone)]
+pub struct BitSlice<'a> {
+  bitstring: &'a Bitstring,
+  start: usize,
+  len: usize,
+}
+
+impl <'a> BitSlice<'a> {
+  pub fn len(&self) -> usize { self.len }
+  pub fn is_empty(&self) -> bool { self.len == 0 }
+
+  pub fn bit(&self, i: usize) -> Bit {
+    assert!(i < self.len, "Index {} out of range for length {}", i, self.len);
+    self.bitstring.bit(self.start + i)
+  }
+
+  pub fn iter(&self) -> Iter<'a> {
+    Iter {
+      bitstring: self.bitstring,
+      index: self.start,
+      end: self.start + self.len,
+    }
+  }
+
+  /// Returns a view of the bits with indices in [start, end) of this slice.
+  pub fn slice(&self, start: usize, end: usize) -> BitSlice<'a> {
+    assert!(start <= end && end <= self.len,
+        "Range [{}, {}) out of range for length {}", start, end, self.len);
+    BitSlice {
+      bitstring: self.bitstring,
+      start: self.start + start,
+      len: end - start,
+    }
+  }
+
+  /// Like Bitstring::extract_u64, with indices relative to this slice.
+  pub fn extract_u64(&self, start: usize, num_bits: usize) -> u64 {
+    assert!(start + num_bits <= self.len,
+        "Range [{}, {}) out of range for length {}",
+        start, start + num_bits, self.len);
+    self.bitstring.extract_u64(self.start + start, num_bits)
+  }
+
+  /// Returns a copy of the bits in this slice.
+  pub fn to_bitstring(&self) -> Bitstring {
+    let mut result = Bitstring::new();
+    result.append_slice(*self);
+    return result;
+  }
+}
+
+impl <'a> fmt::Display for BitSlice<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for bit in self.iter() {
+      f.write_str(match bit {
+        Bit::Zero => "0",
+        Bit::One => "1",
+      })?;
+    }
+    Ok(())
+  }
+}
+
+impl <'a> fmt::Debug for BitSlice<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "BitSlice({})", self)
+  }
+}
+
+impl <'a> PartialEq for BitSlice<'a> {
+  fn eq(&self, other: &BitSlice<'a>) -> bool {
+    self.len == other.len && self.iter().eq(other.iter())
+  }
+}
+
+impl <'a> Eq for BitSlice<'a> {}
+
+impl <'a> Hash for BitSlice<'a> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.to_bitstring().hash(state);
+  }
+}
+
+impl <'a> Index<usize> for BitSlice<'a> {
+  type Output = Bit;
+
+  fn index(&self, i: usize) -> &Bit {
+    match self.bit(i) {
+      Bit::Zero => &ZERO,
+      Bit::One => &ONE,
+    }
+  }
+}
+
+impl <'a> IntoIterator for BitSlice<'a> {
+  type Item = Bit;
+  type IntoIter = Iter<'a>;
+
+  fn into_iter(self) -> Iter<'a> {
+    self.iter()
+  }
+}
+
+/// Iterates over the bits of a bitstring or slice.
+pub struct Iter<'a> {
+  bitstring: &'a Bitstring,
+  index: usize,
+  end: usize,
+}
+
+impl <'a> Iterator for Iter<'a> {
+  type Item = Bit;
+
+  fn next(&mut self) -> Option<Bit> {
+    if self.index >= self.end {
+      return None;
+    }
+    let bit = self.bitstring.bit(self.index);
+    self.index = self.index + 1;
+    return Some(bit);
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let remaining = self.end - self.index;
+    (remaining, Some(remaining))
+  }
+}
+
+/// Returned when parsing a string which is not a sequence of zeroes and ones.
+#[derive(Debug, PartialEq)]
+pub struct ParseBitstringError {
+  character: char,
+  position: usize,
+}
+
+impl fmt::Display for ParseBitstringError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "invalid character '{}' at position {}",
+        self.character, self.position)
+  }
+}
+
+impl Error for ParseBitstringError {}
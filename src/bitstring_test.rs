@@ -20,6 +20,8 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use std::collections::HashSet;
+
 use bitstring::Bitstring;
 use bitstring::Bit;
 
@@ -63,3 +65,202 @@ fn from_string() {
   let bits = Bitstring::create_from_string("01001");
   assert_eq!("01001", bits.to_string());
 }
+
+#[test]
+fn pop() {
+  let mut bits = Bitstring::create_from_string("0110");
+  assert_eq!(Bit::Zero, bits.pop());
+  assert_eq!(Bit::One, bits.pop());
+  assert_eq!("01", bits.to_string());
+}
+
+#[test]
+fn across_words() {
+  let mut expected = String::new();
+  let mut bits = Bitstring::new();
+  for i in 0..150 {
+    let one = i % 3 == 0 || i % 7 == 0;
+    bits.push(if one { Bit::One } else { Bit::Zero });
+    expected.push(if one { '1' } else { '0' });
+  }
+  assert_eq!(150, bits.len());
+  assert_eq!(expected, bits.to_string());
+
+  for _ in 0..100 {
+    bits.pop();
+  }
+  assert_eq!(&expected[..50], bits.to_string());
+}
+
+#[test]
+fn push_u64() {
+  let mut bits = Bitstring::create_from_string("1");
+  bits.push_u64(5, 4);
+  bits.push_u64(0, 0);
+  assert_eq!("10101", bits.to_string());
+}
+
+#[test]
+fn push_u64_across_words() {
+  let mut bits = Bitstring::create_from_string(&"0".repeat(60));
+  bits.push_u64(0b1011011, 7);
+  bits.push_u64(u64::max_value(), 64);
+  assert_eq!(131, bits.len());
+  assert_eq!("1011011", &bits.to_string()[60..67]);
+  assert_eq!("1".repeat(64), &bits.to_string()[67..]);
+}
+
+#[test]
+fn extract_u64() {
+  let bits = Bitstring::create_from_string("0010110");
+  assert_eq!(5, bits.extract_u64(2, 3));
+  assert_eq!(22, bits.extract_u64(0, 7));
+  assert_eq!(0, bits.extract_u64(3, 0));
+}
+
+#[test]
+fn extract_u64_across_words() {
+  let mut bits = Bitstring::create_from_string(&"1".repeat(62));
+  bits.push_u64(0xABCDEF, 24);
+  assert_eq!(0xABCDEF, bits.extract_u64(62, 24));
+  assert_eq!(0x3, bits.extract_u64(60, 2));
+}
+
+#[test]
+#[should_panic]
+fn extract_out_of_range() {
+  let bits = Bitstring::create_from_string("0010110");
+  bits.extract_u64(5, 3);
+}
+
+#[test]
+fn append_bitstring() {
+  let mut bits = Bitstring::create_from_string("101");
+  let other_string = "01".repeat(40);
+  bits.append(&Bitstring::create_from_string(&other_string));
+  assert_eq!(format!("101{}", other_string), bits.to_string());
+}
+
+#[test]
+fn iter() {
+  let bits = Bitstring::create_from_string("1101");
+  let collected: Vec<Bit> = bits.iter().collect();
+  assert_eq!(vec!(Bit::One, Bit::One, Bit::Zero, Bit::One), collected);
+}
+
+#[test]
+fn parse() {
+  let bits: Bitstring = "1001".parse().unwrap();
+  assert_eq!("1001", bits.to_string());
+  assert!("10a1".parse::<Bitstring>().is_err());
+  assert!("".parse::<Bitstring>().unwrap().is_empty());
+}
+
+#[test]
+fn display() {
+  let bits = Bitstring::create_from_string("0110");
+  assert_eq!("0110", format!("{}", bits));
+  assert_eq!("Bitstring(0110)", format!("{:?}", bits));
+}
+
+#[test]
+fn index() {
+  let bits = Bitstring::create_from_string("0110");
+  assert_eq!(Bit::Zero, bits[0]);
+  assert_eq!(Bit::One, bits[2]);
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_range() {
+  let bits = Bitstring::create_from_string("0110");
+  let _ = bits[4];
+}
+
+#[test]
+fn equality() {
+  let mut bits = Bitstring::create_from_string("01101");
+  assert_eq!(Bitstring::create_from_string("01101"), bits);
+  assert!(Bitstring::create_from_string("0110") != bits);
+
+  // Bits removed by pop must not affect equality or hashing.
+  bits.pop();
+  bits.push(Bit::Zero);
+  assert_eq!(Bitstring::create_from_string("01100"), bits);
+
+  let mut set = HashSet::new();
+  set.insert(bits);
+  assert!(set.contains(&Bitstring::create_from_string("01100")));
+  assert!(!set.contains(&Bitstring::create_from_string("011000")));
+}
+
+#[test]
+fn slice() {
+  let bits = Bitstring::create_from_string("0011010111");
+  let slice = bits.slice(2, 7);
+  assert_eq!(5, slice.len());
+  assert_eq!("11010", slice.to_string());
+  assert_eq!(Bit::Zero, slice[2]);
+  assert_eq!(0b101, slice.extract_u64(1, 3));
+  assert_eq!("01", slice.slice(2, 4).to_string());
+  assert_eq!(Bitstring::create_from_string("11010"), slice.to_bitstring());
+  assert_eq!(bits.slice(2, 4), bits.slice(7, 9));
+  assert!(bits.slice(2, 4) != bits.slice(3, 5));
+}
+
+#[test]
+fn into_iterator() {
+  let bits = Bitstring::create_from_string("101");
+  let mut ones = 0;
+  for bit in &bits {
+    if bit == Bit::One {
+      ones = ones + 1;
+    }
+  }
+  assert_eq!(2, ones);
+
+  let collected: Bitstring = bits.slice(1, 3).into_iter().collect();
+  assert_eq!("01", collected.to_string());
+}
+
+#[test]
+fn extend_and_concatenate() {
+  let mut bits = Bitstring::create_from_string("10");
+  bits.extend(vec!(Bit::One, Bit::One));
+  assert_eq!("1011", bits.to_string());
+
+  let concatenated = bits + &Bitstring::create_from_string("000");
+  assert_eq!("1011000", concatenated.to_string());
+
+  let mut appended = Bitstring::create_from_string("1");
+  appended.append_slice(concatenated.slice(3, 7));
+  assert_eq!("11000", appended.to_string());
+}
+
+#[test]
+fn bytes() {
+  let bits = Bitstring::create_from_bytes(&[0xA5, 0x0F]);
+  assert_eq!("1010010100001111", bits.to_string());
+  assert_eq!(vec!(0xA5, 0x0F), bits.to_bytes());
+
+  // Partial bytes are padded with zeroes.
+  let partial = Bitstring::create_from_string("1111111111");
+  assert_eq!(vec!(0xFF, 0xC0), partial.to_bytes());
+}
+
+#[test]
+fn fixed_width() {
+  let bits = Bitstring::create_from_u64_fixed(5, 6);
+  assert_eq!("000101", bits.to_string());
+  assert_eq!(5, bits.to_u64());
+  assert_eq!(0, Bitstring::create_empty().to_u64());
+}
+
+#[test]
+fn truncate() {
+  let mut bits = Bitstring::create_from_string(&"1".repeat(100));
+  bits.truncate(70);
+  assert_eq!(Bitstring::create_from_string(&"1".repeat(70)), bits);
+  bits.truncate(80);
+  assert_eq!(70, bits.len());
+}
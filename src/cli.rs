@@ -0,0 +1,361 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Command line interface of the main binary. Every option is passed as a
+//! flag of the form --name=value and has a default, so that running without
+//! any flags reproduces the original hard-coded experiment. Options can also
+//! be read from a config file using --config=path, see the config module.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::f64;
+use std::path::Path;
+use std::str::FromStr;
+
+use log::LogLevelFilter;
+
+use config;
+use environment::registry;
+use explorer::{ExplorerType, MonteCarloParams};
+use predictor::estimator::EstimatorType;
+
+/// All options which control a run of the main binary.
+#[derive(Clone, Debug)]
+pub struct Options {
+  /// The name of the environment, as understood by registry::create.
+  pub environment: String,
+
+  /// Environment specific parameters, set using "environment.<name>".
+  pub environment_params: BTreeMap<String, String>,
+
+  pub context_tree_depth: usize,
+  pub estimator: EstimatorType,
+
+  /// Whether to use a separate context tree for every percept bit.
+  pub factored: bool,
+
+  pub num_cycles: usize,
+
+  /// Seeds the random number generator from which all others are derived.
+  pub seed: usize,
+
+  /// If present, override the seeds otherwise derived from seed.
+  pub agent_seed: Option<usize>,
+  pub environment_seed: Option<usize>,
+
+  pub log_level: LogLevelFilter,
+  pub explorer_type: ExplorerType,
+  pub monte_carlo_params: MonteCarloParams,
+
+  /// The probability of picking a random action instead of consulting the
+  /// explorer, and the factor by which it decays after every action.
+  pub exploration_rate: f64,
+  pub exploration_decay: f64,
+
+  /// Whether to log to stdout.
+  pub log_stdout: bool,
+
+  /// If present, the log is written to this file.
+  pub log_file: Option<String>,
+
+  /// If present, the agent is written to this file after the last cycle.
+  pub checkpoint_file: Option<String>,
+
+  /// If present, the agent is restored from this file instead of being
+  /// created from scratch.
+  pub resume_file: Option<String>,
+
+  /// Whether the user asked for the usage message.
+  pub help: bool,
+
+  /// The names of all agent options which have been set. These cannot be
+  /// combined with resume, which restores the agent from a checkpoint.
+  agent_options: BTreeSet<String>,
+}
+
+impl Options {
+  /// Returns the options used when no flags are passed.
+  pub fn new() -> Self {
+    Options {
+      environment: "coin-flip".to_string(),
+      environment_params: BTreeMap::new(),
+      context_tree_depth: 4,
+      estimator: EstimatorType::KrichevskyTrofimov,
+      factored: false,
+      num_cycles: 10,
+      seed: 5761567,
+      agent_seed: None,
+      environment_seed: None,
+      log_level: LogLevelFilter::Info,
+      explorer_type: ExplorerType::MonteCarlo,
+      monte_carlo_params: MonteCarloParams {
+        horizon: 4,
+        num_simulations: 200,
+        exploration_constant: f64::consts::SQRT_2,
+        reward_range: None,
+      },
+      exploration_rate: 0.0,
+      exploration_decay: 1.0,
+      log_stdout: true,
+      log_file: None,
+      checkpoint_file: None,
+      resume_file: None,
+      help: false,
+      agent_options: BTreeSet::new(),
+    }
+  }
+
+  /// Sets the option with the supplied name. Returns an error describing the
+  /// problem if there is no such option or if the value is invalid.
+  pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+    if name.starts_with(ENVIRONMENT_PREFIX) {
+      let param = &name[ENVIRONMENT_PREFIX.len()..];
+      if param.is_empty() {
+        return Err(format!("Missing parameter name in \"{}\"", name));
+      }
+      self.environment_params.insert(param.to_string(), value.to_string());
+      return Ok(());
+    }
+
+    if AGENT_OPTIONS.contains(&name) {
+      self.agent_options.insert(name.to_string());
+    }
+    match name {
+      "environment" => {
+        if !registry::ENVIRONMENT_NAMES.contains(&value) {
+          return Err(format!("Unknown environment \"{}\", expected one of {}",
+              value, registry::ENVIRONMENT_NAMES.join(", ")));
+        }
+        self.environment = value.to_string();
+      },
+      "depth" => self.context_tree_depth = parse_value(name, value)?,
+      "estimator" => self.estimator = parse_estimator(value)?,
+      "factored" => self.factored = parse_value(name, value)?,
+      "cycles" => self.num_cycles = parse_value(name, value)?,
+      "seed" => self.seed = parse_value(name, value)?,
+      "agent-seed" => self.agent_seed = Some(parse_value(name, value)?),
+      "environment-seed" => {
+        self.environment_seed = Some(parse_value(name, value)?);
+      },
+      "log-level" => {
+        self.log_level = LogLevelFilter::from_str(value).map_err(|_| format!(
+            "Invalid value \"{}\" for log-level, expected one of off, error, \
+             warn, info, debug, trace", value))?;
+      },
+      "explorer" => {
+        self.explorer_type = match value {
+          "monte-carlo" => ExplorerType::MonteCarlo,
+          "random" => ExplorerType::Random,
+          _ => return Err(format!(
+              "Invalid value \"{}\" for explorer, expected monte-carlo or \
+               random", value)),
+        };
+      },
+      "horizon" => {
+        self.monte_carlo_params.horizon = parse_positive(name, value)?;
+      },
+      "simulations" => {
+        self.monte_carlo_params.num_simulations =
+            parse_positive(name, value)?;
+      },
+      "exploration-constant" => {
+        let constant: f64 = parse_value(name, value)?;
+        if constant < 0.0 || !constant.is_finite() {
+          return Err(format!(
+              "Invalid value \"{}\" for {}, expected a non-negative number",
+              value, name));
+        }
+        self.monte_carlo_params.exploration_constant = constant;
+      },
+      "exploration-rate" => {
+        self.exploration_rate = parse_probability(name, value)?;
+      },
+      "exploration-decay" => {
+        self.exploration_decay = parse_probability(name, value)?;
+      },
+      "log-stdout" => self.log_stdout = parse_value(name, value)?,
+      "log-file" => self.log_file = Some(parse_path(name, value)?),
+      "checkpoint" => self.checkpoint_file = Some(parse_path(name, value)?),
+      "resume" => self.resume_file = Some(parse_path(name, value)?),
+      _ => return Err(format!("Unknown option \"{}\"", name)),
+    }
+    return Ok(());
+  }
+}
+
+/// Parses command line arguments, excluding the name of the binary, on top
+/// of the default options.
+pub fn parse(args: &[String]) -> Result<Options, String> {
+  let mut options = Options::new();
+  for arg in args.iter() {
+    if arg == "--help" || arg == "-h" {
+      options.help = true;
+      continue;
+    }
+    if !arg.starts_with("--") {
+      return Err(format!("Unexpected argument \"{}\"", arg));
+    }
+
+    // Flags are applied in order, so flags following --config override the
+    // values in the file.
+    let flag = &arg[2..];
+    match flag.find('=') {
+      Some(index) if &flag[..index] == "config" => {
+        config::load_file(Path::new(&flag[index + 1..]), &mut options)?;
+      },
+      Some(index) => options.set(&flag[..index], &flag[index + 1..])?,
+      None => return Err(format!("Missing value for flag \"{}\"", arg)),
+    }
+  }
+
+  if options.resume_file.is_some() && !options.agent_options.is_empty() {
+    let names: Vec<&str> =
+        options.agent_options.iter().map(|name| name.as_str()).collect();
+    return Err(format!(
+        "Cannot combine resume with agent options, which are taken from the \
+         checkpoint: {}", names.join(", ")));
+  }
+  return Ok(options);
+}
+
+/// Returns a description of all flags.
+pub fn usage() -> String {
+  format!("Usage: ai [--name=value]...
+
+Options:
+  --config=PATH               Read options from a file of name=value lines.
+                              Later flags override the file.
+  --environment=NAME          One of {}. Default: coin-flip
+  --environment.PARAM=VALUE   Sets a parameter of the environment.
+  --depth=N                   Depth of the context tree. Default: 4
+  --estimator=NAME            One of kt, laplace, zero-redundancy or
+                              dirichlet:ALPHA. Default: kt
+  --factored=BOOL             Use one context tree per percept bit.
+                              Default: false
+  --cycles=N                  Number of agent-environment cycles. Default: 10
+  --seed=N                    Seed for all random numbers. Default: 5761567
+  --agent-seed=N              Seed for the agent, derived from --seed if
+                              absent.
+  --environment-seed=N        Seed for the environment, derived from --seed
+                              if absent.
+  --log-level=LEVEL           One of off, error, warn, info, debug, trace.
+                              Default: info
+  --explorer=TYPE             Either monte-carlo or random.
+                              Default: monte-carlo
+  --horizon=N                 Cycles each simulation looks ahead. Default: 4
+  --simulations=N             Simulations per action. Default: 200
+  --exploration-constant=C    UCB exploration constant. Default: 1.414...
+  --exploration-rate=P        Probability of a random action. Default: 0
+  --exploration-decay=F       Factor applied to the exploration rate after
+                              every action. Default: 1
+  --log-stdout=BOOL           Write the log to stdout. Default: true
+  --log-file=PATH             Write the log to PATH.
+  --checkpoint=PATH           Save the agent to PATH after the last cycle.
+  --resume=PATH               Restore the agent from PATH before starting.
+                              Agent options are taken from the checkpoint
+                              and cannot be passed as well.
+  --help                      Print this message.",
+      registry::ENVIRONMENT_NAMES.join(", "))
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+  value.parse::<T>().map_err(|_| format!(
+      "Invalid value \"{}\" for {}", value, name))
+}
+
+fn parse_probability(name: &str, value: &str) -> Result<f64, String> {
+  let result: f64 = parse_value(name, value)?;
+  if !(result >= 0.0 && result <= 1.0) {
+    return Err(format!("Invalid value \"{}\" for {}, must be in [0, 1]",
+        value, name));
+  }
+  return Ok(result);
+}
+
+fn parse_estimator(value: &str) -> Result<EstimatorType, String> {
+  match value {
+    "kt" => Ok(EstimatorType::KrichevskyTrofimov),
+    "laplace" => Ok(EstimatorType::Laplace),
+    "zero-redundancy" => Ok(EstimatorType::ZeroRedundancy),
+    _ if value.starts_with("dirichlet:") => {
+      match value["dirichlet:".len()..].parse::<f64>() {
+        Ok(alpha) if alpha > 0.0 && alpha.is_finite() =>
+            Ok(EstimatorType::Dirichlet(alpha)),
+        _ => Err(format!(
+            "Invalid value \"{}\" for estimator, alpha must be positive",
+            value)),
+      }
+    },
+    _ => Err(format!("Invalid value \"{}\" for estimator, expected one of \
+        kt, laplace, zero-redundancy or dirichlet:ALPHA", value)),
+  }
+}
+
+/// Returns the value accepted by the estimator option for an estimator.
+pub fn format_estimator(estimator: EstimatorType) -> String {
+  match estimator {
+    EstimatorType::KrichevskyTrofimov => "kt".to_string(),
+    EstimatorType::Laplace => "laplace".to_string(),
+    EstimatorType::ZeroRedundancy => "zero-redundancy".to_string(),
+    EstimatorType::Dirichlet(alpha) => format!("dirichlet:{}", alpha),
+  }
+}
+
+/// Returns the value accepted by the explorer option for an explorer.
+pub fn format_explorer_type(explorer_type: ExplorerType) -> &'static str {
+  match explorer_type {
+    ExplorerType::MonteCarlo => "monte-carlo",
+    ExplorerType::Random => "random",
+  }
+}
+
+fn parse_positive(name: &str, value: &str) -> Result<usize, String> {
+  let result: usize = parse_value(name, value)?;
+  if result == 0 {
+    return Err(format!("Invalid value \"{}\" for {}, must be positive",
+        value, name));
+  }
+  return Ok(result);
+}
+
+/// Options which configure the agent rather than the experiment.
+const AGENT_OPTIONS: &[&str] = &[
+  "agent-seed",
+  "depth",
+  "estimator",
+  "explorer",
+  "exploration-constant",
+  "exploration-decay",
+  "exploration-rate",
+  "factored",
+  "horizon",
+  "simulations",
+];
+
+/// Options starting with this prefix are passed on to the environment.
+const ENVIRONMENT_PREFIX: &str = "environment.";
+
+fn parse_path(name: &str, value: &str) -> Result<String, String> {
+  if value.is_empty() {
+    return Err(format!("Missing path for {}", name));
+  }
+  return Ok(value.to_string());
+}
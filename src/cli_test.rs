@@ -0,0 +1,128 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use log::LogLevelFilter;
+
+use cli;
+use explorer::ExplorerType;
+use predictor::estimator::EstimatorType;
+
+#[test]
+fn defaults() {
+  let options = cli::parse(&[]).unwrap();
+  assert_eq!("coin-flip", options.environment);
+  assert_eq!(4, options.context_tree_depth);
+  assert_eq!(10, options.num_cycles);
+  assert_eq!(5761567, options.seed);
+  assert_eq!(LogLevelFilter::Info, options.log_level);
+  assert_eq!(ExplorerType::MonteCarlo, options.explorer_type);
+  assert_eq!(EstimatorType::KrichevskyTrofimov, options.estimator);
+  assert!(!options.factored);
+  assert_eq!(0.0, options.exploration_rate);
+  assert!(options.log_stdout);
+  assert_eq!(None, options.checkpoint_file);
+  assert!(!options.help);
+}
+
+#[test]
+fn parse_flags() {
+  let options = cli::parse(&args(&[
+      "--environment=coin-flip", "--depth=12", "--cycles=1000", "--seed=7",
+      "--log-level=debug", "--explorer=random", "--horizon=6",
+      "--simulations=50", "--exploration-constant=0.5",
+      "--log-file=out.log", "--checkpoint=agent.ckpt"])).unwrap();
+  assert_eq!(12, options.context_tree_depth);
+  assert_eq!(1000, options.num_cycles);
+  assert_eq!(7, options.seed);
+  assert_eq!(LogLevelFilter::Debug, options.log_level);
+  assert_eq!(ExplorerType::Random, options.explorer_type);
+  assert_eq!(6, options.monte_carlo_params.horizon);
+  assert_eq!(50, options.monte_carlo_params.num_simulations);
+  assert_eq!(0.5, options.monte_carlo_params.exploration_constant);
+  assert_eq!(Some("out.log".to_string()), options.log_file);
+  assert_eq!(Some("agent.ckpt".to_string()), options.checkpoint_file);
+}
+
+#[test]
+fn resume() {
+  let options = cli::parse(&args(&[
+      "--environment=grid", "--cycles=20", "--resume=old.ckpt"])).unwrap();
+  assert_eq!(Some("old.ckpt".to_string()), options.resume_file);
+
+  // Agent options would be silently ignored.
+  let result = cli::parse(&args(&[
+      "--resume=old.ckpt", "--horizon=6", "--depth=12", "--horizon=7"]));
+  assert_eq!(Err("Cannot combine resume with agent options, which are taken \
+      from the checkpoint: depth, horizon".to_string()), result.map(|_| ()));
+  assert!(cli::parse(&args(&["--explorer=random", "--resume=old.ckpt"]))
+      .is_err());
+}
+
+#[test]
+fn parse_config_flags() {
+  let options = cli::parse(&args(&[
+      "--environment.size=5", "--estimator=laplace", "--factored=true",
+      "--agent-seed=3", "--environment-seed=4", "--exploration-rate=0.1",
+      "--exploration-decay=0.5", "--log-stdout=false"])).unwrap();
+  assert_eq!(Some(&"5".to_string()), options.environment_params.get("size"));
+  assert_eq!(EstimatorType::Laplace, options.estimator);
+  assert!(options.factored);
+  assert_eq!(Some(3), options.agent_seed);
+  assert_eq!(Some(4), options.environment_seed);
+  assert_eq!(0.1, options.exploration_rate);
+  assert_eq!(0.5, options.exploration_decay);
+  assert!(!options.log_stdout);
+}
+
+#[test]
+fn help() {
+  assert!(cli::parse(&args(&["--help"])).unwrap().help);
+}
+
+#[test]
+fn invalid_flags() {
+  let invalid = [
+    "--unknown=1",
+    "--depth",
+    "depth=3",
+    "--depth=-1",
+    "--simulations=0",
+    "--environment=chess",
+    "--explorer=greedy",
+    "--log-level=loud",
+    "--exploration-constant=-1",
+    "--checkpoint=",
+    "--environment.=1",
+    "--estimator=dirichlet:0",
+    "--estimator=dirichlet:x",
+    "--factored=maybe",
+    "--exploration-rate=1.5",
+    "--exploration-decay=-0.1",
+  ];
+  for flag in invalid.iter() {
+    assert!(cli::parse(&args(&[flag])).is_err(), "Accepted {}", flag);
+  }
+}
+
+fn args(values: &[&str]) -> Vec<String> {
+  values.iter().map(|value| value.to_string()).collect()
+}
@@ -0,0 +1,118 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Experiment configuration files. A config file describes a whole experiment
+//! using the same options as the command line, one per line:
+//!
+//!   # Comments start with a hash.
+//!   environment = coin-flip
+//!   depth = 8
+//!   cycles = 1000
+//!
+//! Blank lines are ignored and later lines override earlier ones.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use cli::{self, Options};
+
+/// Applies the options in the config file at the supplied path.
+pub fn load_file(path: &Path, options: &mut Options) -> Result<(), String> {
+  let mut contents = String::new();
+  File::open(path)
+      .and_then(|mut file| file.read_to_string(&mut contents))
+      .map_err(|error| format!(
+          "Unable to read config file {}: {}", path.display(), error))?;
+  apply(&contents, &path.display().to_string(), options)
+}
+
+/// Applies the options in a config file with the supplied contents. Errors
+/// are prefixed with the source and the line number.
+pub fn apply(
+    contents: &str, source: &str, options: &mut Options) -> Result<(), String> {
+  for (index, line) in contents.lines().enumerate() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    let result = match line.find('=') {
+      Some(position) =>
+          options.set(line[..position].trim(), line[position + 1..].trim()),
+      None => Err(format!("Expected name = value but got \"{}\"", line)),
+    };
+    result.map_err(|message| format!("{}:{}: {}", source, index + 1, message))?;
+  }
+  return Ok(());
+}
+
+/// Returns a config file which resolves to the supplied options. Agent
+/// options are left out when resuming, since they come from the checkpoint.
+pub fn format(options: &Options) -> String {
+  let mut lines = Vec::new();
+  lines.push(format!("environment = {}", options.environment));
+  for (name, value) in options.environment_params.iter() {
+    lines.push(format!("environment.{} = {}", name, value));
+  }
+  lines.push(format!("cycles = {}", options.num_cycles));
+  lines.push(format!("seed = {}", options.seed));
+  if let Some(seed) = options.environment_seed {
+    lines.push(format!("environment-seed = {}", seed));
+  }
+  lines.push(format!(
+      "log-level = {}", options.log_level.to_string().to_lowercase()));
+  lines.push(format!("log-stdout = {}", options.log_stdout));
+  if options.resume_file.is_none() {
+    format_agent_options(options, &mut lines);
+  }
+  let paths = [
+    ("log-file", &options.log_file),
+    ("checkpoint", &options.checkpoint_file),
+    ("resume", &options.resume_file),
+  ];
+  for &(name, path) in paths.iter() {
+    if let Some(ref path) = *path {
+      lines.push(format!("{} = {}", name, path));
+    }
+  }
+  return lines.join("\n");
+}
+
+fn format_agent_options(options: &Options, lines: &mut Vec<String>) {
+  lines.push(format!("depth = {}", options.context_tree_depth));
+  lines.push(format!(
+      "estimator = {}", cli::format_estimator(options.estimator)));
+  lines.push(format!("factored = {}", options.factored));
+  if let Some(seed) = options.agent_seed {
+    lines.push(format!("agent-seed = {}", seed));
+  }
+  lines.push(format!(
+      "explorer = {}", cli::format_explorer_type(options.explorer_type)));
+  lines.push(format!("horizon = {}", options.monte_carlo_params.horizon));
+  lines.push(format!(
+      "simulations = {}", options.monte_carlo_params.num_simulations));
+  lines.push(format!("exploration-constant = {}",
+      options.monte_carlo_params.exploration_constant));
+  lines.push(format!("exploration-rate = {}", options.exploration_rate));
+  lines.push(format!("exploration-decay = {}", options.exploration_decay));
+}
@@ -0,0 +1,116 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::fs::File;
+use std::io::Write;
+
+use cli::{self, Options};
+use config;
+use predictor::estimator::EstimatorType;
+
+#[test]
+fn apply() {
+  let mut options = Options::new();
+  let contents = "
+      # An experiment.
+      environment = coin-flip
+      environment.bias=0.7
+
+      depth = 8
+      estimator = dirichlet:0.25
+      cycles = 100
+      cycles = 200
+  ";
+  config::apply(contents, "test.cfg", &mut options).unwrap();
+  assert_eq!(8, options.context_tree_depth);
+  assert_eq!(EstimatorType::Dirichlet(0.25), options.estimator);
+  assert_eq!(200, options.num_cycles);
+  assert_eq!(Some(&"0.7".to_string()), options.environment_params.get("bias"));
+}
+
+#[test]
+fn errors_name_line() {
+  let mut options = Options::new();
+  let contents = "depth = 3\n\ncolor = red\n";
+  let result = config::apply(contents, "test.cfg", &mut options);
+  assert_eq!(Err("test.cfg:3: Unknown option \"color\"".to_string()), result);
+
+  let result = config::apply("depth 3", "test.cfg", &mut options);
+  assert!(result.unwrap_err().starts_with("test.cfg:1: Expected name = value"));
+}
+
+#[test]
+fn format_round_trip() {
+  let mut options = Options::new();
+  config::apply("
+      environment.bias = 0.7
+      estimator = zero-redundancy
+      factored = true
+      agent-seed = 12
+      log-level = debug
+      explorer = random
+      exploration-rate = 0.2
+      exploration-decay = 0.99
+      checkpoint = agent.ckpt
+  ", "test.cfg", &mut options).unwrap();
+
+  let formatted = config::format(&options);
+  let mut restored = Options::new();
+  config::apply(&formatted, "formatted", &mut restored).unwrap();
+  assert_eq!(formatted, config::format(&restored));
+  assert!(formatted.contains("estimator = zero-redundancy"));
+  assert!(formatted.contains("log-level = debug"));
+}
+
+#[test]
+fn format_resume() {
+  let mut options = Options::new();
+  config::apply("
+      depth = 12
+      resume = agent.ckpt
+  ", "test.cfg", &mut options).unwrap();
+
+  // Agent options come from the checkpoint, so the resolved configuration
+  // can be passed back in without conflicting with resume.
+  let formatted = config::format(&options);
+  assert!(formatted.contains("resume = agent.ckpt"));
+  assert!(!formatted.contains("depth"));
+  let mut restored = Options::new();
+  config::apply(&formatted, "formatted", &mut restored).unwrap();
+  assert_eq!(formatted, config::format(&restored));
+}
+
+#[test]
+fn config_flag() {
+  let path = env::temp_dir().join("config_test_config_flag.cfg");
+  File::create(&path).unwrap().write_all(b"depth = 7\ncycles = 5\n").unwrap();
+
+  // Flags after --config override the file.
+  let args = vec!(
+      format!("--config={}", path.display()), "--cycles=9".to_string());
+  let options = cli::parse(&args).unwrap();
+  assert_eq!(7, options.context_tree_depth);
+  assert_eq!(9, options.num_cycles);
+
+  assert!(cli::parse(&["--config=/does/not/exist.cfg".to_string()]).is_err());
+}
@@ -0,0 +1,151 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::io::{self, Read, Write};
+
+use bitstring::Bitstring;
+use serialization;
+use types::{Action, Observation, Reward};
+
+/// Defines how actions and percepts are represented as bits. Every component
+/// is encoded as an unsigned integer of fixed width, most significant bit
+/// first, so that bit strings can be decoded unambiguously. A percept is
+/// the observation bits followed by the reward bits.
+///
+/// Rewards are shifted by a fixed offset before encoding so that negative
+/// rewards can be represented. After shifting, rewards must be integral.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Encoder {
+  action_bits: usize,
+  observation_bits: usize,
+  reward_bits: usize,
+  reward_offset: f64,
+}
+
+impl Encoder {
+  pub fn new(
+      action_bits: usize,
+      observation_bits: usize,
+      reward_bits: usize,
+      reward_offset: f64) -> Self {
+    // Actions are non-negative i16 values, observations non-negative i32
+    // values.
+    assert!(action_bits <= 15 && observation_bits <= 31 && reward_bits <= 63);
+    Encoder {
+      action_bits: action_bits,
+      observation_bits: observation_bits,
+      reward_bits: reward_bits,
+      reward_offset: reward_offset,
+    }
+  }
+
+  /// Writes the widths of all components, followed by the reward offset.
+  pub fn save(&self, writer: &mut Write) -> io::Result<()> {
+    serialization::write_u8(writer, self.action_bits as u8)?;
+    serialization::write_u8(writer, self.observation_bits as u8)?;
+    serialization::write_u8(writer, self.reward_bits as u8)?;
+    serialization::write_f64(writer, self.reward_offset)
+  }
+
+  pub fn load(reader: &mut Read) -> io::Result<Encoder> {
+    let action_bits = serialization::read_u8(reader)? as usize;
+    let observation_bits = serialization::read_u8(reader)? as usize;
+    let reward_bits = serialization::read_u8(reader)? as usize;
+    let reward_offset = serialization::read_f64(reader)?;
+    if action_bits > 15 || observation_bits > 31 || reward_bits > 63 {
+      return Err(serialization::invalid_data("Invalid encoder widths"));
+    }
+    return Ok(Encoder::new(
+        action_bits, observation_bits, reward_bits, reward_offset));
+  }
+
+  pub fn action_bits(&self) -> usize { self.action_bits }
+  pub fn observation_bits(&self) -> usize { self.observation_bits }
+  pub fn reward_bits(&self) -> usize { self.reward_bits }
+  pub fn reward_offset(&self) -> f64 { self.reward_offset }
+
+  /// Returns the total number of bits in an encoded percept.
+  pub fn percept_bits(&self) -> usize {
+    self.observation_bits + self.reward_bits
+  }
+
+  /// Returns the smallest reward which can be encoded.
+  pub fn min_reward(&self) -> Reward {
+    Reward(-self.reward_offset)
+  }
+
+  /// Returns the largest reward which can be encoded.
+  pub fn max_reward(&self) -> Reward {
+    let max_value = (1u64 << self.reward_bits) - 1;
+    Reward(max_value as f64 - self.reward_offset)
+  }
+
+  pub fn encode_action(&self, action: Action) -> Bitstring {
+    let Action(value) = action;
+    assert!(value >= 0, "Cannot encode negative action {}", value);
+    let mut result = Bitstring::new();
+    result.push_u64(value as u64, self.action_bits);
+    return result;
+  }
+
+  pub fn decode_action(&self, bits: &Bitstring) -> Action {
+    assert_eq!(self.action_bits, bits.len());
+    return Action(bits.extract_u64(0, self.action_bits) as i16);
+  }
+
+  pub fn encode_percept(
+      &self, observation: Observation, reward: Reward) -> Bitstring {
+    let Observation(observation_value) = observation;
+    assert!(observation_value >= 0,
+        "Cannot encode negative observation {}", observation_value);
+
+    let Reward(reward_value) = reward;
+    let shifted = reward_value + self.reward_offset;
+    assert!(shifted >= 0.0 && shifted == shifted.round(),
+        "Cannot encode reward {} with offset {}",
+        reward_value, self.reward_offset);
+
+    let mut result = Bitstring::new();
+    result.push_u64(observation_value as u64, self.observation_bits);
+    result.push_u64(shifted as u64, self.reward_bits);
+    return result;
+  }
+
+  pub fn decode_percept(&self, bits: &Bitstring) -> (Observation, Reward) {
+    assert_eq!(self.percept_bits(), bits.len());
+    let observation = bits.extract_u64(0, self.observation_bits);
+    let reward = bits.extract_u64(self.observation_bits, self.reward_bits);
+    return (
+        Observation(observation as i32),
+        Reward(reward as f64 - self.reward_offset));
+  }
+}
+
+/// Returns the number of bits required to represent all values in
+/// [0, value]. Always returns at least 1.
+pub fn bits_required(value: u64) -> usize {
+  let mut result = 1;
+  while result < 64 && value >> result > 0 {
+    result = result + 1;
+  }
+  return result;
+}
@@ -0,0 +1,116 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bitstring::Bitstring;
+use encoding::{bits_required, Encoder};
+use types::{Action, Observation, Reward};
+
+#[test]
+fn encode_action() {
+  let encoder = Encoder::new(3, 1, 1, 0.0);
+  assert_eq!("101", encoder.encode_action(Action(5)).to_string());
+  assert_eq!("000", encoder.encode_action(Action(0)).to_string());
+}
+
+#[test]
+fn decode_action() {
+  let encoder = Encoder::new(3, 1, 1, 0.0);
+  let Action(value) =
+      encoder.decode_action(&Bitstring::create_from_string("011"));
+  assert_eq!(3, value);
+}
+
+#[test]
+fn encode_percept() {
+  let encoder = Encoder::new(1, 2, 3, 0.0);
+  let bits = encoder.encode_percept(Observation(2), Reward(5.0));
+  assert_eq!("10101", bits.to_string());
+}
+
+#[test]
+fn negative_reward() {
+  let encoder = Encoder::new(1, 2, 4, 10.0);
+  let bits = encoder.encode_percept(Observation(1), Reward(-7.0));
+  assert_eq!("010011", bits.to_string());
+
+  let (Observation(observation), Reward(reward)) =
+      encoder.decode_percept(&bits);
+  assert_eq!(1, observation);
+  assert_eq!(-7.0, reward);
+}
+
+#[test]
+fn reward_range() {
+  let encoder = Encoder::new(1, 2, 4, 10.0);
+  assert_eq!(Reward(-10.0), encoder.min_reward());
+  assert_eq!(Reward(5.0), encoder.max_reward());
+}
+
+#[test]
+fn percept_round_trip() {
+  let encoder = Encoder::new(2, 4, 5, 3.0);
+  for observation in 0..16 {
+    for reward in -3..29 {
+      let bits = encoder.encode_percept(
+          Observation(observation), Reward(reward as f64));
+      assert_eq!(encoder.percept_bits(), bits.len());
+
+      let (Observation(o), Reward(r)) = encoder.decode_percept(&bits);
+      assert_eq!(observation, o);
+      assert_eq!(reward as f64, r);
+    }
+  }
+}
+
+#[test]
+fn wide_observation() {
+  let encoder = Encoder::new(4, 18, 3, 3.0);
+  let bits = encoder.encode_percept(Observation(0x2aaaa), Reward(2.0));
+  assert_eq!("101010101010101010101", bits.to_string());
+
+  let (Observation(observation), _) = encoder.decode_percept(&bits);
+  assert_eq!(0x2aaaa, observation);
+}
+
+#[test]
+#[should_panic]
+fn observation_too_large() {
+  let encoder = Encoder::new(1, 2, 4, 0.0);
+  encoder.encode_percept(Observation(4), Reward(0.0));
+}
+
+#[test]
+#[should_panic]
+fn reward_below_offset() {
+  let encoder = Encoder::new(1, 2, 4, 2.0);
+  encoder.encode_percept(Observation(0), Reward(-3.0));
+}
+
+#[test]
+fn required_bits() {
+  assert_eq!(1, bits_required(0));
+  assert_eq!(1, bits_required(1));
+  assert_eq!(2, bits_required(2));
+  assert_eq!(2, bits_required(3));
+  assert_eq!(3, bits_required(4));
+  assert_eq!(64, bits_required(u64::max_value()));
+}
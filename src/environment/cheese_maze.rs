@@ -0,0 +1,161 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use random::Random;
+use types::{Action, Observation, Reward};
+
+/// The layout of the maze. Walls are marked with '#', the cheese with 'C'.
+const MAZE: [&str; 5] = [
+  "#######",
+  "#     #",
+  "# # # #",
+  "# #C# #",
+  "#######",
+];
+
+const WALL_REWARD: f64 = -10.0;
+const MOVE_REWARD: f64 = -1.0;
+const CHEESE_REWARD: f64 = 10.0;
+
+/// The Cheese Maze from "A Monte-Carlo AIXI Approximation" by Veness et al.,
+/// originally due to McCallum. The agent is a mouse which moves through a
+/// small maze in search of cheese. It only observes which of its four
+/// neighbouring cells are walls, so several cells look the same and the
+/// agent has to use its history to tell them apart. Finding the cheese ends
+/// the episode, after which the mouse is placed in a random free cell.
+///
+/// Actions: 0 moves up, 1 right, 2 down and 3 left. Observations: four bits
+/// indicating walls in the same order, with "up" as the most significant bit.
+pub struct CheeseMaze {
+  random: Box<Random>,
+  row: usize,
+  column: usize,
+  last_reward: Reward,
+}
+
+impl CheeseMaze {
+  pub fn new(random: Box<Random>) -> CheeseMaze {
+    let mut result = CheeseMaze {
+      random: random,
+      row: 0,
+      column: 0,
+      last_reward: Reward(0.0),
+    };
+    result.reset();
+    return result;
+  }
+
+  /// Moves the mouse to a random free cell other than the cheese.
+  fn place_randomly(&mut self) {
+    let mut cells = Vec::new();
+    for (row, line) in MAZE.iter().enumerate() {
+      for (column, cell) in line.chars().enumerate() {
+        if cell == ' ' {
+          cells.push((row, column));
+        }
+      }
+    }
+    let index = self.random.next_modulo(cells.len() as u64) as usize;
+    let (row, column) = cells[index];
+    self.row = row;
+    self.column = column;
+  }
+
+  /// Returns the contents of the cell next to the mouse in the supplied
+  /// direction. Directions are numbered like actions.
+  fn neighbour(&self, direction: i16) -> (usize, usize, char) {
+    let (row, column) = match direction {
+      0 => (self.row - 1, self.column),
+      1 => (self.row, self.column + 1),
+      2 => (self.row + 1, self.column),
+      3 => (self.row, self.column - 1),
+      _ => panic!("Invalid direction {}", direction),
+    };
+    (row, column, cell(row, column))
+  }
+}
+
+/// Returns the contents of the maze at the supplied position.
+fn cell(row: usize, column: usize) -> char {
+  MAZE[row].as_bytes()[column] as char
+}
+
+impl Environment for CheeseMaze {
+  fn num_actions(&self) -> i16 {
+    return 4;
+  }
+
+  fn reward(&self) -> Reward {
+    self.last_reward
+  }
+
+  fn min_reward(&self) -> Reward {
+    Reward(WALL_REWARD)
+  }
+
+  fn max_reward(&self) -> Reward {
+    Reward(CHEESE_REWARD)
+  }
+
+  fn min_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn max_observation(&self) -> Observation {
+    Observation(0b1111)
+  }
+
+  fn observation(&self) -> Observation {
+    let mut result = 0;
+    for direction in 0..4 {
+      let (_, _, contents) = self.neighbour(direction);
+      result = (result << 1) | if contents == '#' { 1 } else { 0 };
+    }
+    return Observation(result);
+  }
+
+  fn update(&mut self, action: Action) {
+    let Action(a) = action;
+    debug_assert!(a >= 0 && a < self.num_actions());
+
+    let (row, column, contents) = self.neighbour(a);
+    let reward = match contents {
+      '#' => WALL_REWARD,
+      'C' => CHEESE_REWARD,
+      _ => MOVE_REWARD,
+    };
+    if contents != '#' {
+      self.row = row;
+      self.column = column;
+    }
+    self.last_reward = Reward(reward);
+  }
+
+  fn is_terminal(&self) -> bool {
+    cell(self.row, self.column) == 'C'
+  }
+
+  fn reset(&mut self) {
+    self.place_randomly();
+  }
+}
@@ -0,0 +1,68 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::cheese_maze::CheeseMaze;
+use environment::environment::Environment;
+use environment::test_util::{assert_observation, FakeRandom};
+use types::{Action, Reward};
+
+const UP: Action = Action(0);
+const RIGHT: Action = Action(1);
+const DOWN: Action = Action(2);
+
+#[test]
+fn observations() {
+  // Start in the top left corner, with walls above and to the left.
+  let mut maze = CheeseMaze::new(Box::new(FakeRandom::new(vec!(0), vec!())));
+  assert_observation(0b1001, &maze);
+
+  maze.update(RIGHT);
+  assert_eq!(Reward(-1.0), maze.reward());
+  assert_observation(0b1010, &maze);
+}
+
+#[test]
+fn walls() {
+  let mut maze = CheeseMaze::new(Box::new(FakeRandom::new(vec!(0), vec!())));
+  maze.update(UP);
+  assert_eq!(Reward(-10.0), maze.reward());
+  assert_observation(0b1001, &maze);
+}
+
+#[test]
+fn cheese() {
+  // Start right above the cheese.
+  let mut maze = CheeseMaze::new(Box::new(FakeRandom::new(vec!(6), vec!())));
+  assert_observation(0b0101, &maze);
+
+  maze.update(DOWN);
+  assert_eq!(Reward(10.0), maze.reward());
+  assert_observation(0b0111, &maze);
+  assert!(maze.is_terminal());
+
+  // The mouse is placed in a free cell, here the same one again.
+  maze.reset();
+  assert!(!maze.is_terminal());
+  assert_observation(0b0101, &maze);
+  maze.update(DOWN);
+  assert!(maze.is_terminal());
+}
@@ -20,6 +20,7 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use encoding;
 use random::Random;
 use types::{Action, Observation, Reward};
 
@@ -32,15 +33,56 @@ pub trait Environment {
   fn observation(&self) -> Observation;
 
   fn update(&mut self, action: Action);
+
+  /// The range of rewards this environment can hand out in a single cycle.
+  /// All rewards in the range must differ from the minimum by an integer.
+  fn min_reward(&self) -> Reward;
+  fn max_reward(&self) -> Reward;
+
+  /// The range of observations this environment can produce. Observations
+  /// are never negative.
+  fn min_observation(&self) -> Observation;
+  fn max_observation(&self) -> Observation;
+
+  /// The number of bits needed to represent every valid observation.
+  fn num_observation_bits(&self) -> usize {
+    let Observation(max) = self.max_observation();
+    return encoding::bits_required(max as u64);
+  }
+
+  /// The number of bits needed to represent every valid reward, after
+  /// shifting it by the minimum reward.
+  fn num_reward_bits(&self) -> usize {
+    let Reward(min) = self.min_reward();
+    let Reward(max) = self.max_reward();
+    return encoding::bits_required((max - min) as u64);
+  }
+
+  /// Indicates whether the current episode is over. Once this returns true,
+  /// reset must be called before the next update. Environments without
+  /// episodes are never terminal.
+  fn is_terminal(&self) -> bool {
+    false
+  }
+
+  /// Starts a new episode. Afterwards, observation returns the first
+  /// observation of the new episode.
+  fn reset(&mut self) {}
 }
 
 /// An environment in which the observations represent repeated
 /// tosses of a coin. Actions which guess the outcome of the coin
 /// toss are rewarded.
-pub struct CoinFlip<'a> {
+///
+/// Actions: 0 guesses heads and 1 tails. Observations: 0 is heads and 1 is
+/// tails.
+pub struct CoinFlip {
+  random: Box<Random>,
+  heads_probability: f64,
+  correct_reward: f64,
+  incorrect_reward: f64,
   last_toss: CoinToss,
   last_guess: Option<CoinToss>,
-  random: &'a mut Random,
 }
 
 /// The possible outcomes of a single coin toss.
@@ -50,28 +92,59 @@ enum CoinToss {
   Tails,
 }
 
-impl <'a> CoinFlip<'a> {
-  pub fn new(random: &'a mut Random) -> CoinFlip<'a> {
+impl CoinFlip {
+  /// Creates a coin which lands heads with the supplied probability. The
+  /// rewards must differ by an integer so that they can be encoded.
+  pub fn new(
+      random: Box<Random>,
+      heads_probability: f64,
+      correct_reward: f64,
+      incorrect_reward: f64) -> CoinFlip {
+    assert!(heads_probability >= 0.0 && heads_probability <= 1.0);
+    assert!((correct_reward - incorrect_reward).fract() == 0.0);
     CoinFlip {
+      random: random,
+      heads_probability: heads_probability,
+      correct_reward: correct_reward,
+      incorrect_reward: incorrect_reward,
       last_toss: CoinToss::Tails,
       last_guess: None,
-      random: random,
     }
   }
 }
 
-impl <'a> Environment for CoinFlip<'a> {
+impl Environment for CoinFlip {
   fn num_actions(&self) -> i16 {
     return 2;
   }
 
   fn reward(&self) -> Reward {
     Reward(match self.last_guess {
-      Some(val) => if val == self.last_toss { 11.0 } else { 10.0 },
+      Some(val) => if val == self.last_toss {
+        self.correct_reward
+      } else {
+        self.incorrect_reward
+      },
       _ => 0.0,
     })
   }
 
+  fn min_reward(&self) -> Reward {
+    Reward(self.correct_reward.min(self.incorrect_reward))
+  }
+
+  fn max_reward(&self) -> Reward {
+    Reward(self.correct_reward.max(self.incorrect_reward))
+  }
+
+  fn min_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn max_observation(&self) -> Observation {
+    Observation(1)
+  }
+
   fn observation(&self) -> Observation {
     match self.last_toss {
       CoinToss::Heads => Observation(0),
@@ -89,10 +162,10 @@ impl <'a> Environment for CoinFlip<'a> {
       _ => None,
     };
 
-    self.last_toss = match self.random.next_modulo(2) {
-      0 => CoinToss::Heads,
-      1 => CoinToss::Tails,
-      _ => panic!(),
+    self.last_toss = if self.random.next_f64() < self.heads_probability {
+      CoinToss::Heads
+    } else {
+      CoinToss::Tails
     };
   }
 }
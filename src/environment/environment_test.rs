@@ -0,0 +1,66 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::{CoinFlip, Environment};
+use environment::test_util::{assert_observation, FakeRandom};
+use types::{Action, Reward};
+
+const HEADS: Action = Action(0);
+const TAILS: Action = Action(1);
+
+#[test]
+fn coin_flip_rewards() {
+  let random = FakeRandom::new(vec!(), vec!(0.3));
+  let mut coin = CoinFlip::new(Box::new(random), 0.5, 1.0, -1.0);
+  coin.update(HEADS);
+  assert_observation(0, &coin);
+  assert_eq!(Reward(1.0), coin.reward());
+
+  coin.update(TAILS);
+  assert_eq!(Reward(-1.0), coin.reward());
+}
+
+#[test]
+fn coin_flip_biased() {
+  // The same draw lands tails on a coin which rarely shows heads.
+  let random = FakeRandom::new(vec!(), vec!(0.3));
+  let mut coin = CoinFlip::new(Box::new(random), 0.2, 11.0, 10.0);
+  coin.update(TAILS);
+  assert_observation(1, &coin);
+  assert_eq!(Reward(11.0), coin.reward());
+}
+
+#[test]
+fn coin_flip_bounds() {
+  let random = FakeRandom::new(vec!(), vec!(0.0));
+  let coin = CoinFlip::new(Box::new(random), 0.5, -2.0, 3.0);
+  assert_eq!(Reward(-2.0), coin.min_reward());
+  assert_eq!(Reward(3.0), coin.max_reward());
+  assert_eq!(3, coin.num_reward_bits());
+  assert_eq!(1, coin.num_observation_bits());
+}
+
+#[test]
+#[should_panic]
+fn coin_flip_invalid_probability() {
+  CoinFlip::new(Box::new(FakeRandom::new(vec!(), vec!(0.0))), 1.5, 1.0, 0.0);
+}
@@ -0,0 +1,162 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use random::Random;
+use types::{Action, Observation, Reward};
+
+/// The probability that listening reveals the correct door.
+const LISTEN_ACCURACY: f64 = 0.85;
+
+const INVALID_REWARD: f64 = -10.0;
+const LISTEN_REWARD: f64 = -1.0;
+const STAND_REWARD: f64 = -1.0;
+const GOLD_REWARD: f64 = 30.0;
+const TIGER_REWARD: f64 = -100.0;
+
+/// The Extended Tiger problem from "A Monte-Carlo AIXI Approximation" by
+/// Veness et al. A tiger hides behind one of two doors, gold behind the
+/// other. The agent starts out sitting and can only listen while sitting,
+/// which reveals the tiger's position with some noise. Doors can only be
+/// opened while standing. Opening a door ends the episode, after which the
+/// tiger moves to a random door and the agent sits down again.
+///
+/// Actions: 0 stands up, 1 listens, 2 opens the left door and 3 opens the
+/// right door. Observations: 0 if nothing was heard, 1 if the tiger was heard
+/// behind the left door and 2 if it was heard behind the right door.
+pub struct ExtendedTiger {
+  random: Box<Random>,
+  tiger: Door,
+  standing: bool,
+  door_opened: bool,
+  last_observation: Observation,
+  last_reward: Reward,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Door {
+  Left,
+  Right,
+}
+
+impl ExtendedTiger {
+  pub fn new(random: Box<Random>) -> ExtendedTiger {
+    let mut result = ExtendedTiger {
+      random: random,
+      tiger: Door::Left,
+      standing: false,
+      door_opened: false,
+      last_observation: Observation(0),
+      last_reward: Reward(0.0),
+    };
+    result.reset();
+    return result;
+  }
+
+  fn random_door(&mut self) -> Door {
+    if self.random.next_modulo(2) == 0 { Door::Left } else { Door::Right }
+  }
+
+  /// Returns the observation produced by listening, which is correct with
+  /// probability LISTEN_ACCURACY.
+  fn listen(&mut self) -> Observation {
+    let correct = self.random.next_f64() < LISTEN_ACCURACY;
+    let heard = match (self.tiger, correct) {
+      (Door::Left, true) | (Door::Right, false) => Door::Left,
+      (Door::Right, true) | (Door::Left, false) => Door::Right,
+    };
+    match heard {
+      Door::Left => Observation(1),
+      Door::Right => Observation(2),
+    }
+  }
+
+  /// Opens the supplied door, ending the episode. Returns the reward for
+  /// opening the door.
+  fn open(&mut self, door: Door) -> f64 {
+    self.door_opened = true;
+    if door == self.tiger { TIGER_REWARD } else { GOLD_REWARD }
+  }
+}
+
+impl Environment for ExtendedTiger {
+  fn num_actions(&self) -> i16 {
+    return 4;
+  }
+
+  fn reward(&self) -> Reward {
+    self.last_reward
+  }
+
+  fn min_reward(&self) -> Reward {
+    Reward(TIGER_REWARD)
+  }
+
+  fn max_reward(&self) -> Reward {
+    Reward(GOLD_REWARD)
+  }
+
+  fn min_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn max_observation(&self) -> Observation {
+    Observation(2)
+  }
+
+  fn observation(&self) -> Observation {
+    self.last_observation
+  }
+
+  fn update(&mut self, action: Action) {
+    let Action(a) = action;
+    debug_assert!(a >= 0 && a < self.num_actions());
+
+    let mut observation = Observation(0);
+    let reward = match (a, self.standing) {
+      (0, false) => {
+        self.standing = true;
+        STAND_REWARD
+      },
+      (1, false) => {
+        observation = self.listen();
+        LISTEN_REWARD
+      },
+      (2, true) => self.open(Door::Left),
+      (3, true) => self.open(Door::Right),
+      _ => INVALID_REWARD,
+    };
+    self.last_observation = observation;
+    self.last_reward = Reward(reward);
+  }
+
+  fn is_terminal(&self) -> bool {
+    self.door_opened
+  }
+
+  fn reset(&mut self) {
+    self.tiger = self.random_door();
+    self.standing = false;
+    self.door_opened = false;
+    self.last_observation = Observation(0);
+  }
+}
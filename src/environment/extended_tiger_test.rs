@@ -0,0 +1,85 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use environment::extended_tiger::ExtendedTiger;
+use environment::test_util::{assert_observation, FakeRandom};
+use types::{Action, Reward};
+
+const STAND: Action = Action(0);
+const LISTEN: Action = Action(1);
+const OPEN_LEFT: Action = Action(2);
+const OPEN_RIGHT: Action = Action(3);
+
+#[test]
+fn listen() {
+  // The tiger is always behind the left door.
+  let random = FakeRandom::new(vec!(), vec!(0.5));
+  let mut tiger = ExtendedTiger::new(Box::new(random));
+  tiger.update(LISTEN);
+  assert_observation(1, &tiger);
+  assert_eq!(Reward(-1.0), tiger.reward());
+
+  // Listening is noisy.
+  let random = FakeRandom::new(vec!(), vec!(0.9));
+  let mut tiger = ExtendedTiger::new(Box::new(random));
+  tiger.update(LISTEN);
+  assert_observation(2, &tiger);
+}
+
+#[test]
+fn open_doors() {
+  let random = FakeRandom::new(vec!(), vec!(0.5));
+  let mut tiger = ExtendedTiger::new(Box::new(random));
+  tiger.update(STAND);
+  assert_eq!(Reward(-1.0), tiger.reward());
+  assert!(!tiger.is_terminal());
+  tiger.update(OPEN_RIGHT);
+  assert_eq!(Reward(30.0), tiger.reward());
+  assert_observation(0, &tiger);
+  assert!(tiger.is_terminal());
+
+  // The agent sits down again after a reset.
+  tiger.reset();
+  assert!(!tiger.is_terminal());
+  tiger.update(OPEN_LEFT);
+  assert_eq!(Reward(-10.0), tiger.reward());
+  tiger.update(STAND);
+  tiger.update(OPEN_LEFT);
+  assert_eq!(Reward(-100.0), tiger.reward());
+  assert!(tiger.is_terminal());
+}
+
+#[test]
+fn invalid_actions() {
+  let random = FakeRandom::new(vec!(), vec!(0.5));
+  let mut tiger = ExtendedTiger::new(Box::new(random));
+  tiger.update(OPEN_LEFT);
+  assert_eq!(Reward(-10.0), tiger.reward());
+
+  tiger.update(STAND);
+  tiger.update(STAND);
+  assert_eq!(Reward(-10.0), tiger.reward());
+  tiger.update(LISTEN);
+  assert_eq!(Reward(-10.0), tiger.reward());
+  assert_observation(0, &tiger);
+}
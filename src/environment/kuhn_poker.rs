@@ -0,0 +1,158 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use random::Random;
+use types::{Action, Observation, Reward};
+
+/// The agent's actions. Passing after a bet folds, betting after a bet calls.
+const PASS: i16 = 0;
+const BET: i16 = 1;
+
+/// Kuhn Poker, as used in "A Monte-Carlo AIXI Approximation" by Veness et al.
+/// There are three cards, jack, queen and king. Both players ante one chip
+/// and receive one card. The opponent acts first, then the agent, and if
+/// the agent bets after a pass, the opponent decides whether to call. The
+/// opponent follows a Nash equilibrium strategy parameterized by alpha in
+/// [0, 1/3]. Every game is an episode lasting a single cycle, the next game
+/// is dealt on reset.
+///
+/// Actions: 0 passes (or folds) and 1 bets (or calls). Observations: the
+/// agent's card (0 for jack, 1 for queen, 2 for king) in the two most
+/// significant bits, followed by a bit which is set if the opponent bet.
+/// Rewards: the number of chips won or lost.
+pub struct KuhnPoker {
+  random: Box<Random>,
+  alpha: f64,
+  agent_card: u64,
+  opponent_card: u64,
+  opponent_bet: bool,
+  game_over: bool,
+  last_reward: Reward,
+}
+
+impl KuhnPoker {
+  pub fn new(random: Box<Random>, alpha: f64) -> KuhnPoker {
+    assert!(alpha >= 0.0 && alpha <= 1.0 / 3.0);
+    let mut result = KuhnPoker {
+      random: random,
+      alpha: alpha,
+      agent_card: 0,
+      opponent_card: 0,
+      opponent_bet: false,
+      game_over: false,
+      last_reward: Reward(0.0),
+    };
+    result.reset();
+    return result;
+  }
+
+  /// Deals a new game and plays the opponent's first move.
+  fn deal(&mut self) {
+    self.game_over = false;
+    self.agent_card = self.random.next_modulo(3);
+    self.opponent_card =
+        (self.agent_card + 1 + self.random.next_modulo(2)) % 3;
+
+    // Bet with a jack as a bluff, never with a queen, and with a king often
+    // enough to make the bluffs worthwhile.
+    let bet_prob = match self.opponent_card {
+      0 => self.alpha,
+      1 => 0.0,
+      _ => 3.0 * self.alpha,
+    };
+    self.opponent_bet = self.random.next_f64() < bet_prob;
+  }
+
+  /// Returns whether the opponent calls a bet of the agent after passing.
+  fn opponent_calls(&mut self) -> bool {
+    let call_prob = match self.opponent_card {
+      0 => 0.0,
+      1 => self.alpha + 1.0 / 3.0,
+      _ => 1.0,
+    };
+    self.random.next_f64() < call_prob
+  }
+
+  /// Returns the agent's winnings if the cards are revealed with the supplied
+  /// number of chips in the pot from each player.
+  fn showdown(&self, stake: f64) -> f64 {
+    if self.agent_card > self.opponent_card { stake } else { -stake }
+  }
+}
+
+impl Environment for KuhnPoker {
+  fn num_actions(&self) -> i16 {
+    return 2;
+  }
+
+  fn reward(&self) -> Reward {
+    self.last_reward
+  }
+
+  // At most two chips change hands in a single game.
+  fn min_reward(&self) -> Reward {
+    Reward(-2.0)
+  }
+
+  fn max_reward(&self) -> Reward {
+    Reward(2.0)
+  }
+
+  fn min_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn max_observation(&self) -> Observation {
+    Observation(0b101)
+  }
+
+  fn observation(&self) -> Observation {
+    let bet = if self.opponent_bet { 1 } else { 0 };
+    Observation(((self.agent_card << 1) | bet) as i32)
+  }
+
+  fn update(&mut self, action: Action) {
+    let Action(a) = action;
+    debug_assert!(a >= 0 && a < self.num_actions());
+
+    let reward = match (self.opponent_bet, a) {
+      (true, PASS) => -1.0,
+      (true, BET) => self.showdown(2.0),
+      (false, PASS) => self.showdown(1.0),
+      (false, BET) => {
+        if self.opponent_calls() { self.showdown(2.0) } else { 1.0 }
+      },
+      _ => panic!("Invalid action {}", a),
+    };
+    self.last_reward = Reward(reward);
+    self.game_over = true;
+  }
+
+  fn is_terminal(&self) -> bool {
+    self.game_over
+  }
+
+  fn reset(&mut self) {
+    self.deal();
+  }
+}
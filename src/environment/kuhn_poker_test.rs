@@ -0,0 +1,88 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use environment::kuhn_poker::KuhnPoker;
+use environment::test_util::{assert_observation, FakeRandom};
+use types::{Action, Reward};
+
+const ALPHA: f64 = 1.0 / 6.0;
+const PASS: Action = Action(0);
+const BET: Action = Action(1);
+
+#[test]
+fn call_bet() {
+  // The agent holds the king, the opponent bluffs with the jack.
+  let random = FakeRandom::new(vec!(2, 0), vec!(0.0));
+  let mut poker = KuhnPoker::new(Box::new(random), ALPHA);
+  assert_observation(0b101, &poker);
+  assert!(!poker.is_terminal());
+
+  poker.update(BET);
+  assert_eq!(Reward(2.0), poker.reward());
+  assert!(poker.is_terminal());
+
+  // The next game is dealt on reset, here the agent gets the jack.
+  poker.reset();
+  assert!(!poker.is_terminal());
+  assert_observation(0b000, &poker);
+}
+
+#[test]
+fn fold() {
+  let random = FakeRandom::new(vec!(2, 0), vec!(0.0));
+  let mut poker = KuhnPoker::new(Box::new(random), ALPHA);
+  poker.update(PASS);
+  assert_eq!(Reward(-1.0), poker.reward());
+}
+
+#[test]
+fn opponent_calls() {
+  // The agent holds the jack, the opponent passes with the king and then
+  // calls the agent's bet.
+  let random = FakeRandom::new(vec!(0, 1), vec!(0.9, 0.5));
+  let mut poker = KuhnPoker::new(Box::new(random), ALPHA);
+  assert_observation(0b000, &poker);
+
+  poker.update(BET);
+  assert_eq!(Reward(-2.0), poker.reward());
+}
+
+#[test]
+fn opponent_folds() {
+  // The agent holds the queen, the opponent passes with the jack and folds
+  // to the agent's bet.
+  let random = FakeRandom::new(vec!(1, 1), vec!(0.9, 0.0));
+  let mut poker = KuhnPoker::new(Box::new(random), ALPHA);
+  assert_observation(0b010, &poker);
+
+  poker.update(BET);
+  assert_eq!(Reward(1.0), poker.reward());
+}
+
+#[test]
+fn showdown_after_passes() {
+  let random = FakeRandom::new(vec!(1, 1), vec!(0.9));
+  let mut poker = KuhnPoker::new(Box::new(random), ALPHA);
+  poker.update(PASS);
+  assert_eq!(Reward(1.0), poker.reward());
+}
@@ -0,0 +1,212 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use random::Random;
+use types::{Action, Observation, Reward};
+
+const GOAL_REWARD: f64 = 1.0;
+const MOVE_REWARD: f64 = 0.0;
+
+/// The 1D maze from "A Monte-Carlo AIXI Approximation" by Veness et al. The
+/// agent moves along a row of cells towards a goal it cannot see. Reaching
+/// the goal is rewarded and ends the episode, after which the agent is placed
+/// in a random cell other than the goal. The goal stays in the same, randomly
+/// chosen cell.
+///
+/// Actions: 0 moves left and 1 moves right. Observations are always 0.
+pub struct OneDimensionalMaze {
+  grid: GridWalk,
+}
+
+impl OneDimensionalMaze {
+  pub fn new(random: Box<Random>, size: usize) -> OneDimensionalMaze {
+    OneDimensionalMaze {
+      grid: GridWalk::new(random, 1, size),
+    }
+  }
+}
+
+impl Environment for OneDimensionalMaze {
+  fn num_actions(&self) -> i16 {
+    return 2;
+  }
+
+  fn reward(&self) -> Reward {
+    self.grid.last_reward
+  }
+
+  fn min_reward(&self) -> Reward {
+    Reward(MOVE_REWARD)
+  }
+
+  fn max_reward(&self) -> Reward {
+    Reward(GOAL_REWARD)
+  }
+
+  fn min_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn max_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn update(&mut self, action: Action) {
+    let Action(a) = action;
+    debug_assert!(a >= 0 && a < self.num_actions());
+    self.grid.step(if a == 0 { (0, -1) } else { (0, 1) });
+  }
+
+  fn is_terminal(&self) -> bool {
+    self.grid.at_goal()
+  }
+
+  fn reset(&mut self) {
+    self.grid.place_randomly();
+  }
+}
+
+/// The grid from "A Monte-Carlo AIXI Approximation" by Veness et al., which
+/// is the 1D maze in two dimensions. The agent moves through a square grid
+/// towards a goal it cannot see.
+///
+/// Actions: 0 moves up, 1 right, 2 down and 3 left. Moving into the edge of
+/// the grid does nothing. Observations are always 0.
+pub struct Grid {
+  grid: GridWalk,
+}
+
+impl Grid {
+  pub fn new(random: Box<Random>, size: usize) -> Grid {
+    Grid {
+      grid: GridWalk::new(random, size, size),
+    }
+  }
+}
+
+impl Environment for Grid {
+  fn num_actions(&self) -> i16 {
+    return 4;
+  }
+
+  fn reward(&self) -> Reward {
+    self.grid.last_reward
+  }
+
+  fn min_reward(&self) -> Reward {
+    Reward(MOVE_REWARD)
+  }
+
+  fn max_reward(&self) -> Reward {
+    Reward(GOAL_REWARD)
+  }
+
+  fn min_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn max_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn update(&mut self, action: Action) {
+    let Action(a) = action;
+    debug_assert!(a >= 0 && a < self.num_actions());
+    self.grid.step(match a {
+      0 => (-1, 0),
+      1 => (0, 1),
+      2 => (1, 0),
+      _ => (0, -1),
+    });
+  }
+
+  fn is_terminal(&self) -> bool {
+    self.grid.at_goal()
+  }
+
+  fn reset(&mut self) {
+    self.grid.place_randomly();
+  }
+}
+
+/// State shared by both mazes: an agent walking through a rectangle of cells
+/// in search of a goal.
+struct GridWalk {
+  random: Box<Random>,
+  rows: usize,
+  columns: usize,
+  goal: usize,
+  position: usize,
+  last_reward: Reward,
+}
+
+impl GridWalk {
+  fn new(random: Box<Random>, rows: usize, columns: usize) -> GridWalk {
+    assert!(rows * columns >= 2, "The maze needs at least two cells");
+    let mut result = GridWalk {
+      random: random,
+      rows: rows,
+      columns: columns,
+      goal: 0,
+      position: 0,
+      last_reward: Reward(0.0),
+    };
+    result.goal = result.random.next_modulo((rows * columns) as u64) as usize;
+    result.place_randomly();
+    return result;
+  }
+
+  fn at_goal(&self) -> bool {
+    self.position == self.goal
+  }
+
+  /// Moves the agent to a random cell other than the goal.
+  fn place_randomly(&mut self) {
+    let num_cells = (self.rows * self.columns) as u64;
+    let offset = 1 + self.random.next_modulo(num_cells - 1) as usize;
+    self.position = (self.goal + offset) % num_cells as usize;
+  }
+
+  /// Moves the agent by the supplied number of rows and columns, unless this
+  /// would leave the grid.
+  fn step(&mut self, delta: (i64, i64)) {
+    let (row_delta, column_delta) = delta;
+    let row = (self.position / self.columns) as i64 + row_delta;
+    let column = (self.position % self.columns) as i64 + column_delta;
+    if row >= 0 && row < self.rows as i64 &&
+        column >= 0 && column < self.columns as i64 {
+      self.position = row as usize * self.columns + column as usize;
+    }
+
+    self.last_reward =
+        Reward(if self.at_goal() { GOAL_REWARD } else { MOVE_REWARD });
+  }
+}
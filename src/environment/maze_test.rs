@@ -0,0 +1,75 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use environment::maze::{Grid, OneDimensionalMaze};
+use environment::test_util::FakeRandom;
+use types::{Action, Reward};
+
+#[test]
+fn one_dimensional_maze() {
+  // The goal is in the last cell and the agent starts in the first one.
+  let random = FakeRandom::new(vec!(3, 0, 0, 0), vec!());
+  let mut maze = OneDimensionalMaze::new(Box::new(random), 4);
+
+  // Moving left stays in the first cell.
+  maze.update(Action(0));
+  assert_eq!(Reward(0.0), maze.reward());
+
+  assert_rewards(&mut maze, Action(1), &[0.0, 0.0, 1.0]);
+  assert!(maze.is_terminal());
+
+  // The agent is placed in the first cell again.
+  maze.reset();
+  assert_rewards(&mut maze, Action(1), &[0.0, 0.0, 1.0]);
+  assert!(maze.is_terminal());
+}
+
+#[test]
+fn grid() {
+  // The goal is in the bottom right corner, the agent starts in the top left.
+  let random = FakeRandom::new(vec!(15, 0, 0), vec!());
+  let mut grid = Grid::new(Box::new(random), 4);
+
+  grid.update(Action(0));
+  grid.update(Action(3));
+  assert_eq!(Reward(0.0), grid.reward());
+
+  assert_rewards(&mut grid, Action(1), &[0.0, 0.0, 0.0]);
+  assert_rewards(&mut grid, Action(2), &[0.0, 0.0, 1.0]);
+  assert!(grid.is_terminal());
+}
+
+#[test]
+#[should_panic]
+fn too_small() {
+  OneDimensionalMaze::new(Box::new(FakeRandom::new(vec!(0), vec!())), 1);
+}
+
+fn assert_rewards(
+    environment: &mut Environment, action: Action, expected: &[f64]) {
+  for &reward in expected.iter() {
+    assert!(!environment.is_terminal());
+    environment.update(action);
+    assert_eq!(Reward(reward), environment.reward());
+  }
+}
@@ -20,5 +20,25 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub mod cheese_maze;
 pub mod environment;
+pub mod extended_tiger;
+pub mod kuhn_poker;
+pub mod maze;
+pub mod pac_man;
+pub mod registry;
+pub mod rock_paper_scissors;
+pub mod tic_tac_toe;
 
+// Unit test modules.
+
+#[cfg(test)] mod cheese_maze_test;
+#[cfg(test)] mod environment_test;
+#[cfg(test)] mod extended_tiger_test;
+#[cfg(test)] mod kuhn_poker_test;
+#[cfg(test)] mod maze_test;
+#[cfg(test)] mod pac_man_test;
+#[cfg(test)] mod registry_test;
+#[cfg(test)] mod rock_paper_scissors_test;
+#[cfg(test)] mod test_util;
+#[cfg(test)] mod tic_tac_toe_test;
@@ -0,0 +1,350 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use random::Random;
+use types::{Action, Observation, Reward};
+
+/// The layout of the maze. Walls are marked with '#', power pills with 'P',
+/// the starting cells of the ghosts with 'G' and the starting cell of Pac-Man
+/// with 'M'. The middle row is a tunnel connecting the left and right edges.
+const MAZE: [&str; 17] = [
+  "#################",
+  "#P      #      P#",
+  "# ## ## # ## ## #",
+  "#               #",
+  "# ## # ### # ## #",
+  "#    #  #  #    #",
+  "#### ## # ## ####",
+  "#### #     # ####",
+  "     # GGGG#     ",
+  "#### #     # ####",
+  "#### # ### # ####",
+  "#       #       #",
+  "# ## ## # ## ## #",
+  "#P #    M    # P#",
+  "## # # ### # # ##",
+  "#    #  #  #    #",
+  "#################",
+];
+const SIZE: usize = 17;
+
+const MOVE_REWARD: f64 = -1.0;
+const WALL_REWARD: f64 = -10.0;
+const FOOD_REWARD: f64 = 10.0;
+const GHOST_REWARD: f64 = 30.0;
+const CAUGHT_REWARD: f64 = -50.0;
+const CLEARED_REWARD: f64 = 100.0;
+
+/// The probability that a free cell contains food at the start of an episode.
+const FOOD_PROB: f64 = 0.5;
+
+/// The number of cycles during which ghosts can be eaten after eating a power
+/// pill.
+const POWER_DURATION: usize = 100;
+
+/// Ghosts chase Pac-Man whenever he is at most this far away.
+const CHASE_DISTANCE: usize = 5;
+
+/// The furthest distance at which food can be smelled.
+const SMELL_DISTANCES: [usize; 3] = [2, 3, 4];
+
+/// Partially observable Pac-Man from "A Monte-Carlo AIXI Approximation" by
+/// Veness et al. Pac-Man moves through a fixed maze, eating food and trying
+/// to avoid four ghosts. Ghosts chase Pac-Man when he is close and move at
+/// random otherwise. After eating a power pill, Pac-Man can eat ghosts for a
+/// while. The episode ends when Pac-Man is caught or all food is eaten.
+///
+/// Actions: 0 moves up, 1 right, 2 down and 3 left. Observations have 16
+/// bits, from most to least significant: 4 bits indicating walls next to
+/// Pac-Man, 4 bits indicating a ghost in sight along a corridor, 3 bits
+/// indicating food within Manhattan distance 2, 3 and 4, 4 bits indicating
+/// food in sight along a corridor and 1 bit indicating that a power pill is in
+/// effect. Directions are always ordered up, right, down, left.
+pub struct PacMan {
+  random: Box<Random>,
+  pac_man: Position,
+  ghosts: Vec<Position>,
+  food: Vec<bool>,
+  pills: Vec<bool>,
+
+  /// The number of remaining cycles during which ghosts can be eaten.
+  power_left: usize,
+
+  episode_over: bool,
+
+  last_reward: Reward,
+}
+
+type Position = (usize, usize);
+
+impl PacMan {
+  pub fn new(random: Box<Random>) -> PacMan {
+    let mut result = PacMan {
+      random: random,
+      pac_man: (0, 0),
+      ghosts: Vec::new(),
+      food: vec!(false; SIZE * SIZE),
+      pills: vec!(false; SIZE * SIZE),
+      power_left: 0,
+      episode_over: false,
+      last_reward: Reward(0.0),
+    };
+    result.reset();
+    return result;
+  }
+
+  /// Puts everyone back into their starting positions and places new food.
+  fn start_episode(&mut self) {
+    self.ghosts.clear();
+    self.power_left = 0;
+    self.episode_over = false;
+    for row in 0..SIZE {
+      for column in 0..SIZE {
+        let index = row * SIZE + column;
+        let contents = cell((row, column));
+        self.pills[index] = contents == 'P';
+        self.food[index] =
+            contents == ' ' && self.random.next_f64() < FOOD_PROB;
+        match contents {
+          'G' => self.ghosts.push((row, column)),
+          'M' => self.pac_man = (row, column),
+          _ => {},
+        }
+      }
+    }
+  }
+
+  /// Moves the ghost at the supplied index. Returns the reward for Pac-Man
+  /// if the ghost ran into him, and whether the episode is over.
+  fn move_ghost(&mut self, index: usize) -> (f64, bool) {
+    let ghost = self.ghosts[index];
+    let directions: Vec<i16> = (0..4)
+        .filter(|&direction| cell(neighbour(ghost, direction)) != '#')
+        .collect();
+
+    let distance = manhattan_distance(ghost, self.pac_man);
+    let direction = if self.power_left == 0 && distance <= CHASE_DISTANCE {
+      let pac_man = self.pac_man;
+      *directions.iter()
+          .min_by_key(|&&d| manhattan_distance(neighbour(ghost, d), pac_man))
+          .unwrap()
+    } else {
+      directions[self.random.next_modulo(directions.len() as u64) as usize]
+    };
+
+    self.ghosts[index] = neighbour(ghost, direction);
+    return self.collide(index);
+  }
+
+  /// Resolves a collision between Pac-Man and the ghost at the supplied
+  /// index, if any. Returns the reward for Pac-Man and whether the episode
+  /// is over.
+  fn collide(&mut self, index: usize) -> (f64, bool) {
+    if self.ghosts[index] != self.pac_man {
+      return (0.0, false);
+    }
+    if self.power_left == 0 {
+      return (CAUGHT_REWARD, true);
+    }
+
+    // Eaten ghosts return to their starting cell.
+    let starts: Vec<Position> = all_cells()
+        .filter(|&position| cell(position) == 'G')
+        .collect();
+    self.ghosts[index] = starts[index];
+    return (GHOST_REWARD, false);
+  }
+
+  /// Plays one cycle and returns Pac-Man's reward.
+  fn play(&mut self, direction: i16) -> f64 {
+    let mut reward = 0.0;
+    let target = neighbour(self.pac_man, direction);
+    if cell(target) == '#' {
+      reward += WALL_REWARD;
+    } else {
+      reward += MOVE_REWARD;
+      self.pac_man = target;
+    }
+
+    if self.power_left > 0 {
+      self.power_left -= 1;
+    }
+    let index = self.pac_man.0 * SIZE + self.pac_man.1;
+    if self.food[index] {
+      self.food[index] = false;
+      reward += FOOD_REWARD;
+    }
+    if self.pills[index] {
+      self.pills[index] = false;
+      self.power_left = POWER_DURATION;
+    }
+
+    // Ghosts may be run into by Pac-Man, or run into him themselves.
+    for ghost in 0..self.ghosts.len() {
+      let (collision_reward, caught) = self.collide(ghost);
+      reward += collision_reward;
+      if caught {
+        self.episode_over = true;
+        return reward;
+      }
+    }
+    for ghost in 0..self.ghosts.len() {
+      let (collision_reward, caught) = self.move_ghost(ghost);
+      reward += collision_reward;
+      if caught {
+        self.episode_over = true;
+        return reward;
+      }
+    }
+
+    if !self.food.iter().any(|&food| food) {
+      reward += CLEARED_REWARD;
+      self.episode_over = true;
+    }
+    return reward;
+  }
+
+  /// Indicates whether the supplied predicate holds for any cell visible from
+  /// Pac-Man in the supplied direction, i.e., before the next wall.
+  fn in_sight<F>(&self, direction: i16, predicate: F) -> bool
+      where F: Fn(Position) -> bool {
+    let mut position = neighbour(self.pac_man, direction);
+
+    // The tunnel allows looking all the way around, so stop after one lap.
+    for _ in 0..SIZE {
+      if cell(position) == '#' {
+        return false;
+      }
+      if predicate(position) {
+        return true;
+      }
+      position = neighbour(position, direction);
+    }
+    return false;
+  }
+
+  /// Returns the Manhattan distance from Pac-Man to the closest food, if any.
+  fn food_distance(&self) -> Option<usize> {
+    all_cells()
+        .filter(|&(row, column)| self.food[row * SIZE + column])
+        .map(|position| manhattan_distance(position, self.pac_man))
+        .min()
+  }
+}
+
+impl Environment for PacMan {
+  fn num_actions(&self) -> i16 {
+    return 4;
+  }
+
+  fn reward(&self) -> Reward {
+    self.last_reward
+  }
+
+  fn min_reward(&self) -> Reward {
+    Reward(WALL_REWARD + CAUGHT_REWARD)
+  }
+
+  fn max_reward(&self) -> Reward {
+    // Every ghost can be eaten twice in one cycle: once when Pac-Man moves
+    // and once when the ghost moves.
+    let ghosts = 2.0 * 4.0 * GHOST_REWARD;
+    Reward(MOVE_REWARD + FOOD_REWARD + ghosts + CLEARED_REWARD)
+  }
+
+  fn min_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn max_observation(&self) -> Observation {
+    Observation(0xffff)
+  }
+
+  fn observation(&self) -> Observation {
+    let mut bits = Vec::new();
+    for direction in 0..4 {
+      bits.push(cell(neighbour(self.pac_man, direction)) == '#');
+    }
+    for direction in 0..4 {
+      bits.push(self.in_sight(
+          direction, |position| self.ghosts.contains(&position)));
+    }
+    let food_distance = self.food_distance();
+    for distance in SMELL_DISTANCES.iter() {
+      bits.push(food_distance.map_or(false, |d| d <= *distance));
+    }
+    for direction in 0..4 {
+      bits.push(self.in_sight(
+          direction, |(row, column)| self.food[row * SIZE + column]));
+    }
+    bits.push(self.power_left > 0);
+
+    let value = bits.iter()
+        .fold(0, |result, &bit| (result << 1) | if bit { 1 } else { 0 });
+    return Observation(value);
+  }
+
+  fn update(&mut self, action: Action) {
+    let Action(a) = action;
+    debug_assert!(a >= 0 && a < self.num_actions());
+    self.last_reward = Reward(self.play(a));
+  }
+
+  fn is_terminal(&self) -> bool {
+    self.episode_over
+  }
+
+  fn reset(&mut self) {
+    self.start_episode();
+  }
+}
+
+/// Returns the contents of the maze at the supplied position.
+fn cell(position: Position) -> char {
+  let (row, column) = position;
+  MAZE[row].as_bytes()[column] as char
+}
+
+/// Returns all positions in the maze, row by row.
+fn all_cells() -> Box<Iterator<Item=Position>> {
+  Box::new((0..SIZE).flat_map(|row| (0..SIZE).map(move |column| (row, column))))
+}
+
+/// Returns the position next to the supplied one in the supplied direction.
+/// Moving off the edge of the maze wraps around.
+fn neighbour(position: Position, direction: i16) -> Position {
+  let (row, column) = position;
+  match direction {
+    0 => ((row + SIZE - 1) % SIZE, column),
+    1 => (row, (column + 1) % SIZE),
+    2 => ((row + 1) % SIZE, column),
+    3 => (row, (column + SIZE - 1) % SIZE),
+    _ => panic!("Invalid direction {}", direction),
+  }
+}
+
+fn manhattan_distance(a: Position, b: Position) -> usize {
+  let (a_row, a_column) = a;
+  let (b_row, b_column) = b;
+  return a_row.abs_diff(b_row) + a_column.abs_diff(b_column);
+}
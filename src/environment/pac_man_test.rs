@@ -0,0 +1,68 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use environment::pac_man::PacMan;
+use environment::test_util::{assert_observation, FakeRandom};
+use types::{Action, Reward};
+
+const UP: Action = Action(0);
+const RIGHT: Action = Action(1);
+
+#[test]
+fn initial_observation() {
+  let pac_man = PacMan::new(Box::new(FakeRandom::new(vec!(), vec!(0.0))));
+
+  // Walls above and below, no ghosts in sight, food right next to Pac-Man
+  // and in sight to the left and right, no power pill.
+  assert_observation(0b1010_0000_111_0101_0, &pac_man);
+}
+
+#[test]
+fn wall() {
+  let mut pac_man = PacMan::new(Box::new(FakeRandom::new(vec!(), vec!(0.0))));
+  pac_man.update(UP);
+  assert_eq!(Reward(-10.0), pac_man.reward());
+}
+
+#[test]
+fn eat_food() {
+  let mut pac_man = PacMan::new(Box::new(FakeRandom::new(vec!(), vec!(0.0))));
+  pac_man.update(RIGHT);
+  assert_eq!(Reward(9.0), pac_man.reward());
+
+  // The new cell opens up a corridor above. The eaten food is gone, but
+  // there is more further down the corridors above, to the left and right.
+  assert_observation(0b0010_0000_111_1101_0, &pac_man);
+}
+
+#[test]
+fn cleared() {
+  // Without any food, the maze is cleared right away.
+  let mut pac_man = PacMan::new(Box::new(FakeRandom::new(vec!(), vec!(0.9))));
+  pac_man.update(RIGHT);
+  assert_eq!(Reward(99.0), pac_man.reward());
+  assert!(pac_man.is_terminal());
+
+  pac_man.reset();
+  assert!(!pac_man.is_terminal());
+}
@@ -0,0 +1,184 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use encoding::{self, Encoder};
+use environment::cheese_maze::CheeseMaze;
+use environment::environment::{CoinFlip, Environment};
+use environment::extended_tiger::ExtendedTiger;
+use environment::kuhn_poker::KuhnPoker;
+use environment::maze::{Grid, OneDimensionalMaze};
+use environment::pac_man::PacMan;
+use environment::rock_paper_scissors::RockPaperScissors;
+use environment::tic_tac_toe::TicTacToe;
+use random::RandomImpl;
+use types::Reward;
+
+/// The names of all environments which can be created using create().
+pub const ENVIRONMENT_NAMES: &[&str] = &[
+  "1d-maze",
+  "cheese-maze",
+  "coin-flip",
+  "extended-tiger",
+  "grid",
+  "kuhn-poker",
+  "pac-man",
+  "rock-paper-scissors",
+  "tic-tac-toe",
+];
+
+/// Parameters of an environment, mapping names to unparsed values.
+pub type Params = BTreeMap<String, String>;
+
+/// Creates the environment with the supplied name, along with an encoder
+/// which is able to represent its actions and percepts. Returns an error if
+/// there is no environment with the supplied name or if the parameters are
+/// not valid for the environment. Every environment gets its own child of
+/// the supplied random number generator.
+pub fn create(
+    name: &str,
+    params: &Params,
+    random: &mut RandomImpl) -> Result<(Box<Environment>, Encoder), String> {
+  let environment: Box<Environment> = match name {
+    "1d-maze" => {
+      check_params(name, params, &["size"])?;
+      let size = maze_size(name, params)?;
+      Box::new(OneDimensionalMaze::new(Box::new(random.new_child()), size))
+    },
+    "cheese-maze" => {
+      check_params(name, params, &[])?;
+      Box::new(CheeseMaze::new(Box::new(random.new_child())))
+    },
+    "coin-flip" => {
+      check_params(name, params, &[
+          "heads-probability", "correct-reward", "incorrect-reward"])?;
+      let heads_probability = param(name, params, "heads-probability", 0.5)?;
+      if !(heads_probability >= 0.0 && heads_probability <= 1.0) {
+        return Err(format!(
+            "Parameter heads-probability for environment {} must be in \
+            [0, 1]", name));
+      }
+      let correct_reward = param(name, params, "correct-reward", 11.0)?;
+      let incorrect_reward = param(name, params, "incorrect-reward", 10.0)?;
+      let difference: f64 = correct_reward - incorrect_reward;
+      if !(difference.is_finite() && difference.fract() == 0.0) {
+        return Err(format!(
+            "Rewards for environment {} must differ by an integer", name));
+      }
+      Box::new(CoinFlip::new(
+          Box::new(random.new_child()),
+          heads_probability,
+          correct_reward,
+          incorrect_reward))
+    },
+    "extended-tiger" => {
+      check_params(name, params, &[])?;
+      Box::new(ExtendedTiger::new(Box::new(random.new_child())))
+    },
+    "grid" => {
+      check_params(name, params, &["size"])?;
+      let size = maze_size(name, params)?;
+      Box::new(Grid::new(Box::new(random.new_child()), size))
+    },
+    "kuhn-poker" => {
+      check_params(name, params, &["alpha"])?;
+      let alpha = param(name, params, "alpha", 1.0 / 6.0)?;
+      if !(alpha >= 0.0 && alpha <= 1.0 / 3.0) {
+        return Err(format!(
+            "Parameter alpha for environment {} must be in [0, 1/3]", name));
+      }
+      Box::new(KuhnPoker::new(Box::new(random.new_child()), alpha))
+    },
+    "pac-man" => {
+      check_params(name, params, &[])?;
+      Box::new(PacMan::new(Box::new(random.new_child())))
+    },
+    "rock-paper-scissors" => {
+      check_params(name, params, &[])?;
+      Box::new(RockPaperScissors::new(Box::new(random.new_child())))
+    },
+    "tic-tac-toe" => {
+      check_params(name, params, &[])?;
+      Box::new(TicTacToe::new(Box::new(random.new_child())))
+    },
+    _ => return Err(format!("Unknown environment \"{}\"", name)),
+  };
+  if environment.num_actions() < 1 {
+    return Err(format!("Environment {} has no actions", name));
+  }
+  let encoder = create_encoder(&*environment);
+  return Ok((environment, encoder));
+}
+
+/// Returns an encoder sized according to the metadata of the supplied
+/// environment. Rewards are shifted so that the minimum reward becomes 0.
+/// The environment must have at least one action.
+pub fn create_encoder(environment: &Environment) -> Encoder {
+  assert!(environment.num_actions() >= 1,
+      "Cannot encode the actions of an environment without actions");
+  let action_bits =
+      encoding::bits_required((environment.num_actions() - 1) as u64);
+  let Reward(min_reward) = environment.min_reward();
+  return Encoder::new(
+      action_bits,
+      environment.num_observation_bits(),
+      environment.num_reward_bits(),
+      -min_reward);
+}
+
+/// Returns the parsed value of a parameter, or the supplied default if the
+/// parameter is absent.
+fn param<T: FromStr>(
+    name: &str, params: &Params, key: &str, default: T) -> Result<T, String> {
+  match params.get(key) {
+    Some(value) => value.parse::<T>().map_err(|_| format!(
+        "Invalid value \"{}\" for parameter {} of environment {}",
+        value, key, name)),
+    None => Ok(default),
+  }
+}
+
+/// Returns the size parameter shared by the mazes, which must allow for at
+/// least two cells.
+fn maze_size(name: &str, params: &Params) -> Result<usize, String> {
+  let size = param(name, params, "size", 4)?;
+  if size < 2 {
+    return Err(format!(
+        "Parameter size for environment {} must be at least 2", name));
+  }
+  return Ok(size);
+}
+
+/// Returns an error if any of the parameters is not in the supplied list of
+/// parameters understood by the environment.
+fn check_params(
+    name: &str, params: &Params, known: &[&str]) -> Result<(), String> {
+  for param in params.keys() {
+    if !known.contains(&param.as_str()) {
+      return Err(format!(
+          "Unknown parameter \"{}\" for environment {}", param, name));
+    }
+  }
+  return Ok(());
+}
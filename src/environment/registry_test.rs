@@ -0,0 +1,144 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use encoding::Encoder;
+use environment::environment::Environment;
+use environment::registry::{self, Params, ENVIRONMENT_NAMES};
+use random::{Random, RandomImpl};
+use types::{Action, Observation, Reward};
+
+#[test]
+fn encoders() {
+  let expected = [
+    ("1d-maze", Encoder::new(1, 1, 1, 0.0)),
+    ("cheese-maze", Encoder::new(2, 4, 5, 10.0)),
+    ("coin-flip", Encoder::new(1, 1, 1, -10.0)),
+    ("extended-tiger", Encoder::new(2, 2, 8, 100.0)),
+    ("grid", Encoder::new(2, 1, 1, 0.0)),
+    ("kuhn-poker", Encoder::new(1, 3, 3, 2.0)),
+    ("pac-man", Encoder::new(2, 16, 9, 60.0)),
+    ("rock-paper-scissors", Encoder::new(2, 2, 2, 1.0)),
+    ("tic-tac-toe", Encoder::new(4, 18, 3, 3.0)),
+  ];
+  for &(name, encoder) in expected.iter() {
+    let mut random = RandomImpl::create(42);
+    let (_, actual) =
+        registry::create(name, &Params::new(), &mut random).unwrap();
+    assert_eq!(encoder, actual, "Encoder of {}", name);
+  }
+}
+
+#[test]
+fn percepts_within_bounds() {
+  for name in ENVIRONMENT_NAMES.iter() {
+    let mut random = RandomImpl::create(42);
+    let mut actions = RandomImpl::create(7);
+    let (mut environment, encoder) =
+        registry::create(name, &Params::new(), &mut random).unwrap();
+    let Observation(min_observation) = environment.min_observation();
+    let Observation(max_observation) = environment.max_observation();
+    let Reward(min_reward) = environment.min_reward();
+    let Reward(max_reward) = environment.max_reward();
+
+    for _ in 0..200 {
+      let num_actions = environment.num_actions() as u64;
+      environment.update(Action(actions.next_modulo(num_actions) as i16));
+      let Observation(observation) = environment.observation();
+      let Reward(reward) = environment.reward();
+      assert!(
+          observation >= min_observation && observation <= max_observation,
+          "Observation {} of {}", observation, name);
+      assert!(reward >= min_reward && reward <= max_reward,
+          "Reward {} of {}", reward, name);
+      if environment.is_terminal() {
+        environment.reset();
+      }
+
+      // Panics if the percept cannot be encoded.
+      encoder.encode_percept(environment.observation(), environment.reward());
+    }
+  }
+}
+
+#[test]
+fn coin_flip_params() {
+  let mut params = Params::new();
+  params.insert("heads-probability".to_string(), "0.9".to_string());
+  params.insert("correct-reward".to_string(), "5".to_string());
+  params.insert("incorrect-reward".to_string(), "-3".to_string());
+  let mut random = RandomImpl::create(42);
+  let (environment, encoder) =
+      registry::create("coin-flip", &params, &mut random).unwrap();
+  assert_eq!(Reward(-3.0), environment.min_reward());
+  assert_eq!(Reward(5.0), environment.max_reward());
+  assert_eq!(Encoder::new(1, 1, 4, 3.0), encoder);
+}
+
+#[test]
+fn invalid_coin_flip_params() {
+  let invalid = [
+    ("heads-probability", "1.5"),
+    ("heads-probability", "likely"),
+    ("correct-reward", "10.5"),
+    ("incorrect-reward", "inf"),
+    ("bias", "0.5"),
+  ];
+  for &(key, value) in invalid.iter() {
+    let mut params = Params::new();
+    params.insert(key.to_string(), value.to_string());
+    let mut random = RandomImpl::create(42);
+    assert!(registry::create("coin-flip", &params, &mut random).is_err(),
+        "Accepted {}={}", key, value);
+  }
+}
+
+#[test]
+fn unknown_environment() {
+  let mut random = RandomImpl::create(42);
+  assert!(registry::create("chess", &Params::new(), &mut random).is_err());
+}
+
+#[test]
+#[should_panic(expected = "without actions")]
+fn encoder_without_actions() {
+  registry::create_encoder(&NoActions);
+}
+
+
+// Fake environment in which the agent cannot do anything.
+
+struct NoActions;
+
+impl Environment for NoActions {
+  fn num_actions(&self) -> i16 { 0 }
+  fn reward(&self) -> Reward { Reward(0.0) }
+  fn observation(&self) -> Observation { Observation(0) }
+  fn update(&mut self, action: Action) {
+    #![allow(unused_variables)]
+    panic!("Unexpected update");
+  }
+
+  fn min_reward(&self) -> Reward { Reward(0.0) }
+  fn max_reward(&self) -> Reward { Reward(0.0) }
+  fn min_observation(&self) -> Observation { Observation(0) }
+  fn max_observation(&self) -> Observation { Observation(0) }
+}
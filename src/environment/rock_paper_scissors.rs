@@ -0,0 +1,110 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use random::Random;
+use types::{Action, Observation, Reward};
+
+const ROCK: i16 = 0;
+
+const WIN_REWARD: f64 = 1.0;
+const DRAW_REWARD: f64 = 0.0;
+const LOSS_REWARD: f64 = -1.0;
+
+/// Rock-Paper-Scissors against a biased opponent, as in "A Monte-Carlo AIXI
+/// Approximation" by Veness et al. The opponent plays uniformly at random,
+/// except that it plays rock again after winning with rock. An agent can
+/// only beat chance by remembering the previous round.
+///
+/// Actions and observations (the opponent's move): 0 is rock, 1 is paper and
+/// 2 is scissors.
+pub struct RockPaperScissors {
+  random: Box<Random>,
+  opponent_move: i16,
+  opponent_won: bool,
+  last_reward: Reward,
+}
+
+impl RockPaperScissors {
+  pub fn new(random: Box<Random>) -> RockPaperScissors {
+    RockPaperScissors {
+      random: random,
+      opponent_move: ROCK,
+      opponent_won: false,
+      last_reward: Reward(0.0),
+    }
+  }
+}
+
+/// Indicates whether the first move beats the second one.
+fn beats(a: i16, b: i16) -> bool {
+  a == (b + 1) % 3
+}
+
+impl Environment for RockPaperScissors {
+  fn num_actions(&self) -> i16 {
+    return 3;
+  }
+
+  fn reward(&self) -> Reward {
+    self.last_reward
+  }
+
+  fn min_reward(&self) -> Reward {
+    Reward(LOSS_REWARD)
+  }
+
+  fn max_reward(&self) -> Reward {
+    Reward(WIN_REWARD)
+  }
+
+  fn min_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn max_observation(&self) -> Observation {
+    Observation(2)
+  }
+
+  fn observation(&self) -> Observation {
+    Observation(self.opponent_move as i32)
+  }
+
+  fn update(&mut self, action: Action) {
+    let Action(a) = action;
+    debug_assert!(a >= 0 && a < self.num_actions());
+
+    if !(self.opponent_won && self.opponent_move == ROCK) {
+      self.opponent_move = self.random.next_modulo(3) as i16;
+    }
+
+    self.opponent_won = beats(self.opponent_move, a);
+    let reward = if beats(a, self.opponent_move) {
+      WIN_REWARD
+    } else if self.opponent_won {
+      LOSS_REWARD
+    } else {
+      DRAW_REWARD
+    };
+    self.last_reward = Reward(reward);
+  }
+}
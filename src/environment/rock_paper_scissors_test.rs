@@ -0,0 +1,76 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use environment::rock_paper_scissors::RockPaperScissors;
+use environment::test_util::{assert_observation, FakeRandom};
+use types::{Action, Reward};
+
+const ROCK: Action = Action(0);
+const PAPER: Action = Action(1);
+const SCISSORS: Action = Action(2);
+
+#[test]
+fn rewards() {
+  let random = FakeRandom::new(vec!(1), vec!());
+  let mut game = RockPaperScissors::new(Box::new(random));
+  game.update(SCISSORS);
+  assert_eq!(Reward(1.0), game.reward());
+  assert_observation(1, &game);
+
+  let random = FakeRandom::new(vec!(1), vec!());
+  let mut game = RockPaperScissors::new(Box::new(random));
+  game.update(ROCK);
+  assert_eq!(Reward(-1.0), game.reward());
+
+  let random = FakeRandom::new(vec!(1), vec!());
+  let mut game = RockPaperScissors::new(Box::new(random));
+  game.update(PAPER);
+  assert_eq!(Reward(0.0), game.reward());
+}
+
+#[test]
+fn repeats_winning_rock() {
+  // The opponent wins with rock and then sticks with it, even though the
+  // random number generator would pick scissors.
+  let random = FakeRandom::new(vec!(0, 2, 2), vec!());
+  let mut game = RockPaperScissors::new(Box::new(random));
+  game.update(SCISSORS);
+  assert_eq!(Reward(-1.0), game.reward());
+  game.update(PAPER);
+  assert_observation(0, &game);
+  assert_eq!(Reward(1.0), game.reward());
+
+  // After losing, the opponent picks at random again.
+  game.update(PAPER);
+  assert_observation(2, &game);
+}
+
+#[test]
+fn only_rock_is_repeated() {
+  let random = FakeRandom::new(vec!(1, 0), vec!());
+  let mut game = RockPaperScissors::new(Box::new(random));
+  game.update(ROCK);
+  assert_eq!(Reward(-1.0), game.reward());
+  game.update(ROCK);
+  assert_observation(0, &game);
+}
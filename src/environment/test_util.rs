@@ -0,0 +1,69 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Helpers shared by the environment tests.
+
+use environment::environment::Environment;
+use random::Random;
+use types::Observation;
+
+pub fn assert_observation(expected: i32, environment: &Environment) {
+  let Observation(actual) = environment.observation();
+  assert_eq!(expected, actual);
+}
+
+// Fake random number generator which returns scripted values. Once a script
+// runs out, its last value is repeated, or zero if the script is empty.
+
+pub struct FakeRandom {
+  values: Vec<u64>,
+  floats: Vec<f64>,
+}
+
+impl FakeRandom {
+  pub fn new(values: Vec<u64>, floats: Vec<f64>) -> Self {
+    FakeRandom {
+      values: values,
+      floats: floats,
+    }
+  }
+}
+
+impl Random for FakeRandom {
+  fn next_modulo(&mut self, limit: u64) -> u64 {
+    let result = next_value(&mut self.values, 0);
+    assert!(result < limit);
+    return result;
+  }
+
+  fn next_f64(&mut self) -> f64 {
+    next_value(&mut self.floats, 0.0)
+  }
+}
+
+fn next_value<T: Copy>(script: &mut Vec<T>, default: T) -> T {
+  match script.len() {
+    0 => default,
+    1 => script[0],
+    _ => script.remove(0),
+  }
+}
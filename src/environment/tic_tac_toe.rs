@@ -0,0 +1,169 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use random::Random;
+use types::{Action, Observation, Reward};
+
+const WIN_REWARD: f64 = 2.0;
+const DRAW_REWARD: f64 = 1.0;
+const LOSS_REWARD: f64 = -2.0;
+const ILLEGAL_REWARD: f64 = -3.0;
+const MOVE_REWARD: f64 = 0.0;
+
+/// All rows, columns and diagonals of the board.
+const LINES: [[usize; 3]; 8] = [
+  [0, 1, 2], [3, 4, 5], [6, 7, 8],
+  [0, 3, 6], [1, 4, 7], [2, 5, 8],
+  [0, 4, 8], [2, 4, 6],
+];
+
+/// Tic-Tac-Toe against an opponent which picks uniformly among the free
+/// cells, as in "A Monte-Carlo AIXI Approximation" by Veness et al. The
+/// agent moves first. Picking an occupied cell is penalized and leaves the
+/// board unchanged. Every game is an episode, the board is cleared on reset.
+///
+/// Actions: the index of the cell to play, row by row. Observations: two bits
+/// per cell, 0 if empty, 1 for the agent and 2 for the opponent, with the
+/// first cell in the most significant position.
+pub struct TicTacToe {
+  random: Box<Random>,
+  board: [Cell; 9],
+  game_over: bool,
+  last_reward: Reward,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Cell {
+  Empty,
+  Agent,
+  Opponent,
+}
+
+impl TicTacToe {
+  pub fn new(random: Box<Random>) -> TicTacToe {
+    TicTacToe {
+      random: random,
+      board: [Cell::Empty; 9],
+      game_over: false,
+      last_reward: Reward(0.0),
+    }
+  }
+
+  /// Indicates whether the supplied player occupies a whole line.
+  fn has_won(&self, player: Cell) -> bool {
+    LINES.iter().any(|line| line.iter().all(|&i| self.board[i] == player))
+  }
+
+  fn free_cells(&self) -> Vec<usize> {
+    (0..9).filter(|&i| self.board[i] == Cell::Empty).collect()
+  }
+
+  /// Returns the reward for the game if it is over after the supplied player
+  /// has moved.
+  fn game_result(&self, player: Cell) -> Option<f64> {
+    if self.has_won(player) {
+      Some(if player == Cell::Agent { WIN_REWARD } else { LOSS_REWARD })
+    } else if self.free_cells().is_empty() {
+      Some(DRAW_REWARD)
+    } else {
+      None
+    }
+  }
+
+  /// Plays the agent's move followed by the opponent's reply and returns the
+  /// agent's reward.
+  fn play(&mut self, cell: usize) -> f64 {
+    if self.board[cell] != Cell::Empty {
+      return ILLEGAL_REWARD;
+    }
+
+    self.board[cell] = Cell::Agent;
+    if let Some(reward) = self.game_result(Cell::Agent) {
+      self.game_over = true;
+      return reward;
+    }
+
+    let free = self.free_cells();
+    let reply = free[self.random.next_modulo(free.len() as u64) as usize];
+    self.board[reply] = Cell::Opponent;
+    if let Some(reward) = self.game_result(Cell::Opponent) {
+      self.game_over = true;
+      return reward;
+    }
+    return MOVE_REWARD;
+  }
+}
+
+impl Environment for TicTacToe {
+  fn num_actions(&self) -> i16 {
+    return 9;
+  }
+
+  fn reward(&self) -> Reward {
+    self.last_reward
+  }
+
+  fn min_reward(&self) -> Reward {
+    Reward(ILLEGAL_REWARD)
+  }
+
+  fn max_reward(&self) -> Reward {
+    Reward(WIN_REWARD)
+  }
+
+  fn min_observation(&self) -> Observation {
+    Observation(0)
+  }
+
+  fn max_observation(&self) -> Observation {
+    // Not a reachable board, but the largest value of the encoding.
+    Observation(0b10_10_10_10_10_10_10_10_10)
+  }
+
+  fn observation(&self) -> Observation {
+    let mut result = 0;
+    for cell in self.board.iter() {
+      result = (result << 2) | match *cell {
+        Cell::Empty => 0,
+        Cell::Agent => 1,
+        Cell::Opponent => 2,
+      };
+    }
+    return Observation(result);
+  }
+
+  fn update(&mut self, action: Action) {
+    let Action(a) = action;
+    debug_assert!(a >= 0 && a < self.num_actions());
+    self.last_reward = Reward(self.play(a as usize));
+  }
+
+  fn is_terminal(&self) -> bool {
+    self.game_over
+  }
+
+  fn reset(&mut self) {
+    self.board = [Cell::Empty; 9];
+    self.game_over = false;
+  }
+}
@@ -0,0 +1,75 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use environment::environment::Environment;
+use environment::tic_tac_toe::TicTacToe;
+use environment::test_util::{assert_observation, FakeRandom};
+use types::{Action, Reward};
+
+#[test]
+fn observation() {
+  let mut game = TicTacToe::new(Box::new(FakeRandom::new(vec!(), vec!())));
+  assert_observation(0, &game);
+
+  // The opponent replies in the first free cell.
+  game.update(Action(4));
+  assert_eq!(Reward(0.0), game.reward());
+  assert_observation(0b10_00_00_00_01_00_00_00_00, &game);
+}
+
+#[test]
+fn illegal_move() {
+  let mut game = TicTacToe::new(Box::new(FakeRandom::new(vec!(), vec!())));
+  game.update(Action(4));
+  game.update(Action(0));
+  assert_eq!(Reward(-3.0), game.reward());
+  assert_observation(0b10_00_00_00_01_00_00_00_00, &game);
+}
+
+#[test]
+fn win_and_reset() {
+  // The opponent fills the top row from the left while the agent takes the
+  // middle row.
+  let mut game = TicTacToe::new(Box::new(FakeRandom::new(vec!(), vec!())));
+  game.update(Action(4));
+  game.update(Action(3));
+  assert_eq!(Reward(0.0), game.reward());
+  game.update(Action(5));
+  assert_eq!(Reward(2.0), game.reward());
+  assert!(game.is_terminal());
+  assert_observation(0b10_10_00_01_01_01_00_00_00, &game);
+
+  game.reset();
+  assert!(!game.is_terminal());
+  assert_observation(0, &game);
+}
+
+#[test]
+fn loss() {
+  let mut game = TicTacToe::new(Box::new(FakeRandom::new(vec!(), vec!())));
+  game.update(Action(8));
+  game.update(Action(7));
+  game.update(Action(5));
+  assert_eq!(Reward(-2.0), game.reward());
+  assert!(game.is_terminal());
+  assert_observation(0b10_10_10_00_00_01_00_01_01, &game);
+}
@@ -0,0 +1,90 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use types::Reward;
+
+/// Keeps track of the episodes of an episodic environment: how many have
+/// been completed, and their returns and lengths.
+pub struct EpisodeStats {
+  num_episodes: u64,
+  total_return: Reward,
+  total_length: u64,
+
+  // The episode in progress.
+  current_return: Reward,
+  current_length: u64,
+}
+
+impl EpisodeStats {
+  pub fn new() -> EpisodeStats {
+    EpisodeStats {
+      num_episodes: 0,
+      total_return: Reward(0.0),
+      total_length: 0,
+      current_return: Reward(0.0),
+      current_length: 0,
+    }
+  }
+
+  /// Adds a cycle with the supplied reward to the episode in progress.
+  pub fn record(&mut self, reward: Reward) {
+    self.current_return = self.current_return + reward;
+    self.current_length += 1;
+  }
+
+  /// Completes the episode in progress and returns its return and length.
+  pub fn end_episode(&mut self) -> (Reward, u64) {
+    let result = (self.current_return, self.current_length);
+    self.num_episodes += 1;
+    self.total_return = self.total_return + self.current_return;
+    self.total_length += self.current_length;
+    self.current_return = Reward(0.0);
+    self.current_length = 0;
+    return result;
+  }
+
+  /// The number of completed episodes.
+  pub fn num_episodes(&self) -> u64 {
+    self.num_episodes
+  }
+
+  /// The length of the episode in progress.
+  pub fn current_length(&self) -> u64 {
+    self.current_length
+  }
+
+  /// The average return of the completed episodes, if any.
+  pub fn average_return(&self) -> Option<Reward> {
+    if self.num_episodes == 0 {
+      return None;
+    }
+    return Some(self.total_return / self.num_episodes as f64);
+  }
+
+  /// The average length of the completed episodes, if any.
+  pub fn average_length(&self) -> Option<f64> {
+    if self.num_episodes == 0 {
+      return None;
+    }
+    return Some(self.total_length as f64 / self.num_episodes as f64);
+  }
+}
@@ -0,0 +1,52 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use episode_stats::EpisodeStats;
+use types::Reward;
+
+#[test]
+fn no_episodes() {
+  let mut stats = EpisodeStats::new();
+  stats.record(Reward(1.0));
+  assert_eq!(0, stats.num_episodes());
+  assert_eq!(1, stats.current_length());
+  assert_eq!(None, stats.average_return());
+  assert_eq!(None, stats.average_length());
+}
+
+#[test]
+fn episodes() {
+  let mut stats = EpisodeStats::new();
+  stats.record(Reward(-1.0));
+  stats.record(Reward(3.0));
+  assert_eq!((Reward(2.0), 2), stats.end_episode());
+  assert_eq!(0, stats.current_length());
+
+  stats.record(Reward(4.0));
+  assert_eq!((Reward(4.0), 1), stats.end_episode());
+
+  // The episode in progress does not count towards the averages.
+  stats.record(Reward(100.0));
+  assert_eq!(2, stats.num_episodes());
+  assert_eq!(Some(Reward(3.0)), stats.average_return());
+  assert_eq!(Some(1.5), stats.average_length());
+}
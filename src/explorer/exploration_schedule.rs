@@ -0,0 +1,75 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::io::{self, Read, Write};
+
+use random::{Random, RandomImpl};
+use serialization;
+
+/// Decides when an agent should ignore its model and pick a random action
+/// instead (epsilon-greedy exploration). The probability of acting randomly
+/// starts at an initial rate and is multiplied by a decay factor after every
+/// decision.
+pub struct ExplorationSchedule {
+  rate: f64,
+  decay: f64,
+  random: RandomImpl,
+}
+
+impl ExplorationSchedule {
+  pub fn new(initial_rate: f64, decay: f64, random: RandomImpl) -> Self {
+    assert!(initial_rate >= 0.0 && initial_rate <= 1.0);
+    assert!(decay >= 0.0 && decay <= 1.0);
+    ExplorationSchedule {
+      rate: initial_rate,
+      decay: decay,
+      random: random,
+    }
+  }
+
+  /// Returns the current probability of picking a random action.
+  pub fn rate(&self) -> f64 { self.rate }
+
+  /// Returns whether the next action should be picked at random and decays
+  /// the rate.
+  pub fn should_explore(&mut self) -> bool {
+    let result = self.random.next_f64() < self.rate;
+    self.rate = self.rate * self.decay;
+    return result;
+  }
+
+  pub fn save(&self, writer: &mut Write) -> io::Result<()> {
+    serialization::write_f64(writer, self.rate)?;
+    serialization::write_f64(writer, self.decay)?;
+    self.random.save(writer)
+  }
+
+  pub fn load(reader: &mut Read) -> io::Result<ExplorationSchedule> {
+    let rate = serialization::read_f64(reader)?;
+    let decay = serialization::read_f64(reader)?;
+    if !(rate >= 0.0 && rate <= 1.0 && decay >= 0.0 && decay <= 1.0) {
+      return Err(serialization::invalid_data("Invalid exploration schedule"));
+    }
+    let random = RandomImpl::load(reader)?;
+    return Ok(ExplorationSchedule::new(rate, decay, random));
+  }
+}
@@ -0,0 +1,43 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use explorer::ExplorationSchedule;
+use random::RandomImpl;
+
+#[test]
+fn extreme_rates() {
+  let mut always = ExplorationSchedule::new(1.0, 1.0, RandomImpl::create(1));
+  let mut never = ExplorationSchedule::new(0.0, 1.0, RandomImpl::create(1));
+  for _ in 0..100 {
+    assert!(always.should_explore());
+    assert!(!never.should_explore());
+  }
+}
+
+#[test]
+fn decay() {
+  let mut schedule = ExplorationSchedule::new(0.8, 0.5, RandomImpl::create(1));
+  schedule.should_explore();
+  assert_eq!(0.4, schedule.rate());
+  schedule.should_explore();
+  assert_eq!(0.2, schedule.rate());
+}
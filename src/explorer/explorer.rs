@@ -28,3 +28,13 @@ pub trait Explorer {
   /// Returns an action within [0, num_actions - 1].
   fn explore(&mut self, num_actions: i16) -> Action;
 }
+
+/// The kinds of explorers an agent can use to pick its actions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ExplorerType {
+  /// Plans ahead using Monte Carlo tree search over the agent's model.
+  MonteCarlo,
+
+  /// Picks actions uniformly at random, ignoring the model.
+  Random,
+}
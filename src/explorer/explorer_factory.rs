@@ -20,8 +20,11 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use std::io::{self, Read, Write};
+
+use encoding::Encoder;
 use explorer::Explorer;
-use explorer::monte_carlo_explorer::MonteCarloExplorer;
+use explorer::monte_carlo_explorer::{MonteCarloExplorer, MonteCarloParams};
 use explorer::random_explorer::RandomExplorer;
 use predictor::Predictor;
 use random::RandomImpl;
@@ -29,24 +32,65 @@ use random::RandomImpl;
 /// An object which knows how to produce explorers.
 pub trait ExplorerFactory {
   fn create_monte_carlo_explorer<'a>(
-      &self, predictor: &'a mut Predictor) -> Box<Explorer + 'a>;
+      &mut self, predictor: &'a mut Predictor) -> Box<Explorer + 'a>;
   fn create_random_explorer(
-      &self) -> Box<Explorer>;
+      &mut self) -> Box<Explorer>;
+
+  /// Writes the configuration and random number generator state of this
+  /// factory, such that ExplorerFactoryImpl::load can restore it. Factories
+  /// which cannot be restored return an error.
+  fn save(&self, writer: &mut Write) -> io::Result<()> {
+    #![allow(unused_variables)]
+    Err(io::Error::new(io::ErrorKind::Other, "not serializable"))
+  }
 }
 
-pub struct ExplorerFactoryImpl;
+pub struct ExplorerFactoryImpl {
+  /// Used to seed the random number generators of the created explorers.
+  random: RandomImpl,
+
+  encoder: Encoder,
+  monte_carlo_params: MonteCarloParams,
+}
 
 impl ExplorerFactoryImpl {
-  pub fn new() -> ExplorerFactoryImpl { ExplorerFactoryImpl }
+  pub fn new(
+      random: RandomImpl,
+      encoder: Encoder,
+      monte_carlo_params: MonteCarloParams) -> ExplorerFactoryImpl {
+    ExplorerFactoryImpl {
+      random: random,
+      encoder: encoder,
+      monte_carlo_params: monte_carlo_params,
+    }
+  }
+
+  /// Restores a factory written by save().
+  pub fn load(reader: &mut Read) -> io::Result<ExplorerFactoryImpl> {
+    let random = RandomImpl::load(reader)?;
+    let encoder = Encoder::load(reader)?;
+    let monte_carlo_params = MonteCarloParams::load(reader)?;
+    return Ok(ExplorerFactoryImpl::new(random, encoder, monte_carlo_params));
+  }
 }
 
 impl ExplorerFactory for ExplorerFactoryImpl {
   fn create_monte_carlo_explorer<'a>(
-      &self, predictor: &'a mut Predictor) -> Box<Explorer + 'a> {
-    Box::new(MonteCarloExplorer::new(predictor))
+      &mut self, predictor: &'a mut Predictor) -> Box<Explorer + 'a> {
+    Box::new(MonteCarloExplorer::new(
+        predictor,
+        self.encoder,
+        Box::new(self.random.new_child()),
+        self.monte_carlo_params))
+  }
+
+  fn create_random_explorer(&mut self) -> Box<Explorer> {
+    Box::new(RandomExplorer::new(Box::new(self.random.new_child())))
   }
 
-  fn create_random_explorer(&self) -> Box<Explorer> {
-    Box::new(RandomExplorer::new(Box::new(RandomImpl::create(235669))))
+  fn save(&self, writer: &mut Write) -> io::Result<()> {
+    self.random.save(writer)?;
+    self.encoder.save(writer)?;
+    self.monte_carlo_params.save(writer)
   }
 }
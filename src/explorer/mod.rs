@@ -21,13 +21,20 @@
 // SOFTWARE.
 
 mod explorer;
-pub use explorer::explorer::Explorer;
+pub use explorer::explorer::{Explorer, ExplorerType};
+
+mod exploration_schedule;
+pub use explorer::exploration_schedule::ExplorationSchedule;
 
 mod explorer_factory;
 pub use explorer::explorer_factory::ExplorerFactory;
 pub use explorer::explorer_factory::ExplorerFactoryImpl;
 
 mod monte_carlo_explorer;
+pub use explorer::monte_carlo_explorer::MonteCarloParams;
+
 mod random_explorer;
 
+#[cfg(test)] mod exploration_schedule_test;
+#[cfg(test)] mod monte_carlo_explorer_test;
 #[cfg(test)] mod random_explorer_test;
@@ -20,26 +20,300 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use std::collections::HashMap;
+use std::f64;
+use std::io::{self, Read, Write};
+
+use bitstring::Bitstring;
+use encoding::Encoder;
 use explorer::Explorer;
 use predictor::Predictor;
-use types::Action;
+use random::Random;
+use serialization;
+use types::{Action, Reward};
+
+/// Parameters which control the search performed by a MonteCarloExplorer.
+#[derive(Copy, Clone, Debug)]
+pub struct MonteCarloParams {
+  /// The number of agent-environment cycles each simulation looks ahead.
+  pub horizon: usize,
+
+  /// The number of simulations to run before picking an action.
+  pub num_simulations: usize,
+
+  /// Controls how much the search favors rarely tried actions over actions
+  /// which have performed well so far (the constant C in UCB).
+  pub exploration_constant: f64,
+
+  /// The range of rewards the environment hands out in a single cycle, used
+  /// to normalize values in UCB. If absent, the wider range of rewards the
+  /// encoder can represent is used instead.
+  pub reward_range: Option<(Reward, Reward)>,
+}
+
+impl MonteCarloParams {
+  pub fn save(&self, writer: &mut Write) -> io::Result<()> {
+    serialization::write_u64(writer, self.horizon as u64)?;
+    serialization::write_u64(writer, self.num_simulations as u64)?;
+    serialization::write_f64(writer, self.exploration_constant)?;
+    match self.reward_range {
+      Some((Reward(min_reward), Reward(max_reward))) => {
+        serialization::write_u8(writer, 1)?;
+        serialization::write_f64(writer, min_reward)?;
+        serialization::write_f64(writer, max_reward)
+      },
+      None => serialization::write_u8(writer, 0),
+    }
+  }
+
+  pub fn load(reader: &mut Read) -> io::Result<MonteCarloParams> {
+    let horizon = serialization::read_u64(reader)? as usize;
+    let num_simulations = serialization::read_u64(reader)? as usize;
+    let exploration_constant = serialization::read_f64(reader)?;
+    let reward_range = if serialization::read_u8(reader)? != 0 {
+      let min_reward = Reward(serialization::read_f64(reader)?);
+      Some((min_reward, Reward(serialization::read_f64(reader)?)))
+    } else {
+      None
+    };
+    Ok(MonteCarloParams {
+      horizon: horizon,
+      num_simulations: num_simulations,
+      exploration_constant: exploration_constant,
+      reward_range: reward_range,
+    })
+  }
+}
 
+/// An explorer which implements the rho-UCT algorithm from "A Monte-Carlo
+/// AIXI Approximation". Builds a search tree of alternating decision and
+/// chance nodes by repeatedly simulating the future using percepts sampled
+/// from the predictor. The history of the predictor is restored after each
+/// simulation.
 pub struct MonteCarloExplorer<'a> {
   predictor: &'a mut Predictor,
+  encoder: Encoder,
+  random: Box<Random>,
+  params: MonteCarloParams,
+
+  /// The number of actions available in the current call to explore.
+  num_actions: i16,
 }
 
 impl <'a> MonteCarloExplorer<'a> {
-  pub fn new(predictor: &mut Predictor) -> MonteCarloExplorer {
+  pub fn new(
+      predictor: &'a mut Predictor,
+      encoder: Encoder,
+      random: Box<Random>,
+      params: MonteCarloParams) -> MonteCarloExplorer<'a> {
     MonteCarloExplorer {
       predictor: predictor,
+      encoder: encoder,
+      random: random,
+      params: params,
+      num_actions: 0,
     }
   }
+
+  /// Runs one simulation starting at the supplied decision node and returns
+  /// the accumulated reward. The horizon is the number of remaining cycles.
+  fn sample_decision(
+      &mut self, node: &mut DecisionNode, horizon: usize) -> f64 {
+    let reward = if horizon == 0 {
+      0.0
+    } else if node.visits == 0 {
+      self.rollout(horizon)
+    } else {
+      let action = self.select_action(node, horizon);
+      let child = node.mut_child(action);
+      self.sample_chance(child, action, horizon)
+    };
+    node.observe(reward);
+    return reward;
+  }
+
+  /// Runs one simulation starting at the supplied chance node, i.e., right
+  /// after the agent has decided to perform the supplied action.
+  fn sample_chance(
+      &mut self, node: &mut ChanceNode, action: Action, horizon: usize) -> f64 {
+    self.perform_action(action);
+    let (percept, reward) = self.sample_percept();
+
+    let child = node.children.entry(percept)
+        .or_insert_with(|| DecisionNode::new(self.num_actions));
+    let total = reward + self.sample_decision(child, horizon - 1);
+    node.observe(total);
+    return total;
+  }
+
+  /// Estimates the value of the current history by acting randomly for the
+  /// remaining number of cycles.
+  fn rollout(&mut self, horizon: usize) -> f64 {
+    let mut total = 0.0;
+    for _ in 0..horizon {
+      let action = self.random_action();
+      self.perform_action(action);
+      let (_, reward) = self.sample_percept();
+      total = total + reward;
+    }
+    return total;
+  }
+
+  /// Picks the next action to simulate from the supplied decision node.
+  /// Actions which have never been tried are picked first, the remaining
+  /// ones are picked according to the UCB formula.
+  fn select_action(&mut self, node: &DecisionNode, horizon: usize) -> Action {
+    let unexplored: Vec<i16> = (0..self.num_actions)
+        .filter(|a| node.child(*a).is_none())
+        .collect();
+    if !unexplored.is_empty() {
+      let index = self.random.next_modulo(unexplored.len() as u64) as usize;
+      return Action(unexplored[index]);
+    }
+
+    // Values are normalized to [0, 1] so that the exploration constant does
+    // not depend on the magnitude of the rewards.
+    let (Reward(min_reward), Reward(max_reward)) = self.params.reward_range
+        .unwrap_or((self.encoder.min_reward(), self.encoder.max_reward()));
+    let min_value = horizon as f64 * min_reward;
+    let value_range = horizon as f64 * (max_reward - min_reward);
+    let log_visits = (node.visits as f64).ln();
+    let mut best_action = 0;
+    let mut best_score = f64::NEG_INFINITY;
+    for a in 0..self.num_actions {
+      let child = node.child(a).unwrap();
+      let exploitation = if value_range > 0.0 {
+        (child.mean - min_value) / value_range
+      } else {
+        0.0
+      };
+      let exploration = self.params.exploration_constant *
+          (log_visits / child.visits as f64).sqrt();
+      let score = exploitation + exploration;
+      if score > best_score {
+        best_score = score;
+        best_action = a;
+      }
+    }
+    return Action(best_action);
+  }
+
+  /// Informs the predictor that the supplied action was performed.
+  fn perform_action(&mut self, action: Action) {
+    let bits = self.encoder.encode_action(action);
+    self.predictor.update_history(&bits);
+  }
+
+  /// Samples a percept from the predictor and adds it to the history.
+  /// Returns the percept bits as well as the decoded reward.
+  fn sample_percept(&mut self) -> (Bitstring, f64) {
+    let percept = self.predictor.sample_and_update_bits(
+        &mut *self.random, self.encoder.percept_bits());
+    let (_, Reward(reward)) = self.encoder.decode_percept(&percept);
+    return (percept, reward);
+  }
+
+  fn random_action(&mut self) -> Action {
+    Action(self.random.next_modulo(self.num_actions as u64) as i16)
+  }
 }
 
 impl <'a> Explorer for MonteCarloExplorer<'a> {
   fn explore(&mut self, num_actions: i16) -> Action {
-    #![allow(unused_variables)]
-    // TODO(dinowernli): Use self.predictor to find the best action.
-    return Action(0);
+    self.num_actions = num_actions;
+
+    let mut root = DecisionNode::new(num_actions);
+    let history_size = self.predictor.history_size();
+    for _ in 0..self.params.num_simulations {
+      self.sample_decision(&mut root, self.params.horizon);
+      self.predictor.revert_to_history_size(history_size);
+    }
+
+    return match root.best_action() {
+      Some(action) => action,
+      None => self.random_action(),
+    };
+  }
+}
+
+/// A node in the search tree in which the agent gets to pick an action.
+struct DecisionNode {
+  visits: u64,
+  mean: f64,
+
+  /// One (lazily created) child per action.
+  children: Vec<Option<ChanceNode>>,
+}
+
+impl DecisionNode {
+  fn new(num_actions: i16) -> Self {
+    DecisionNode {
+      visits: 0,
+      mean: 0.0,
+      children: (0..num_actions).map(|_| None).collect(),
+    }
+  }
+
+  fn child(&self, action: i16) -> Option<&ChanceNode> {
+    self.children[action as usize].as_ref()
+  }
+
+  /// Returns the child for the supplied action, creating it if necessary.
+  fn mut_child(&mut self, action: Action) -> &mut ChanceNode {
+    let Action(value) = action;
+    let child = &mut self.children[value as usize];
+    if child.is_none() {
+      *child = Some(ChanceNode::new());
+    }
+    return child.as_mut().unwrap();
+  }
+
+  /// Returns the action with the highest estimated value, if any action has
+  /// been explored at all.
+  fn best_action(&self) -> Option<Action> {
+    let mut result = None;
+    let mut best_mean = f64::NEG_INFINITY;
+    for (action, child) in self.children.iter().enumerate() {
+      if let Some(ref node) = *child {
+        if node.mean > best_mean {
+          best_mean = node.mean;
+          result = Some(Action(action as i16));
+        }
+      }
+    }
+    return result;
+  }
+
+  /// Incorporates the result of a simulation into the value estimate.
+  fn observe(&mut self, reward: f64) {
+    self.mean = (reward + self.visits as f64 * self.mean) /
+        (self.visits + 1) as f64;
+    self.visits = self.visits + 1;
+  }
+}
+
+/// A node in the search tree in which the environment produces a percept.
+struct ChanceNode {
+  visits: u64,
+  mean: f64,
+
+  /// One child per percept observed so far, keyed by the percept bits.
+  children: HashMap<Bitstring, DecisionNode>,
+}
+
+impl ChanceNode {
+  fn new() -> Self {
+    ChanceNode {
+      visits: 0,
+      mean: 0.0,
+      children: HashMap::new(),
+    }
+  }
+
+  /// Incorporates the result of a simulation into the value estimate.
+  fn observe(&mut self, reward: f64) {
+    self.mean = (reward + self.visits as f64 * self.mean) /
+        (self.visits + 1) as f64;
+    self.visits = self.visits + 1;
   }
 }
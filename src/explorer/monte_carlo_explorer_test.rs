@@ -0,0 +1,148 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::io::Cursor;
+
+use bitstring::{Bit, Bitstring};
+use encoding::Encoder;
+use explorer::Explorer;
+use explorer::monte_carlo_explorer::{MonteCarloExplorer, MonteCarloParams};
+use predictor::Predictor;
+use random::RandomImpl;
+use types::{Action, Reward};
+
+const PARAMS: MonteCarloParams = MonteCarloParams {
+  horizon: 3,
+  num_simulations: 300,
+  exploration_constant: 1.0,
+  reward_range: None,
+};
+
+#[test]
+fn picks_rewarded_action() {
+  let mut predictor = FakePredictor::new(Bit::One);
+  let mut explorer = MonteCarloExplorer::new(
+      &mut predictor, encoder(), Box::new(RandomImpl::create(1234)), PARAMS);
+  let Action(value) = explorer.explore(2);
+  assert_eq!(1, value);
+}
+
+#[test]
+fn picks_other_rewarded_action() {
+  let mut predictor = FakePredictor::new(Bit::Zero);
+  let mut explorer = MonteCarloExplorer::new(
+      &mut predictor, encoder(), Box::new(RandomImpl::create(1234)), PARAMS);
+  let Action(value) = explorer.explore(2);
+  assert_eq!(0, value);
+}
+
+#[test]
+fn picks_rewarded_action_in_reward_range() {
+  // Values are normalized by the supplied range rather than the encoder's.
+  let params = MonteCarloParams {
+    reward_range: Some((Reward(0.0), Reward(4.0))),
+    .. PARAMS
+  };
+  let mut predictor = FakePredictor::new(Bit::One);
+  let mut explorer = MonteCarloExplorer::new(
+      &mut predictor, encoder(), Box::new(RandomImpl::create(1234)), params);
+  let Action(value) = explorer.explore(2);
+  assert_eq!(1, value);
+}
+
+#[test]
+fn save_and_load_params() {
+  for &reward_range in [None, Some((Reward(-2.0), Reward(5.0)))].iter() {
+    let params = MonteCarloParams { reward_range: reward_range, .. PARAMS };
+    let mut bytes = Vec::new();
+    params.save(&mut bytes).unwrap();
+    let loaded = MonteCarloParams::load(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(format!("{:?}", params), format!("{:?}", loaded));
+  }
+}
+
+#[test]
+fn restores_history() {
+  let mut predictor = FakePredictor::new(Bit::One);
+  predictor.update(&Bitstring::create_from_string("011"));
+  {
+    let mut explorer = MonteCarloExplorer::new(
+        &mut predictor, encoder(), Box::new(RandomImpl::create(1234)), PARAMS);
+    explorer.explore(2);
+  }
+  assert_eq!(3, predictor.history_size());
+}
+
+fn encoder() -> Encoder {
+  Encoder::new(1, 1, 1, 0.0)
+}
+
+
+// Fake predictor for a world in which every cycle consists of a one bit
+// action, a one bit observation and a one bit reward. The reward bit is set
+// if and only if the preceding action matches the rewarded action.
+
+struct FakePredictor {
+  history: Vec<Bit>,
+  rewarded_action: Bit,
+}
+
+impl FakePredictor {
+  fn new(rewarded_action: Bit) -> Self {
+    FakePredictor {
+      history: Vec::new(),
+      rewarded_action: rewarded_action,
+    }
+  }
+}
+
+impl Predictor for FakePredictor {
+  fn history_size(&self) -> usize { self.history.len() }
+
+  fn revert_to_history_size(&mut self, target_size: usize) {
+    self.history.truncate(target_size);
+  }
+
+  fn update(&mut self, bits: &Bitstring) {
+    for bit in bits.iter() {
+      self.history.push(bit);
+    }
+  }
+
+  fn update_history(&mut self, bits: &Bitstring) {
+    self.update(bits);
+  }
+
+  fn predict(&mut self, bits: &Bitstring) -> f64 {
+    assert_eq!("1", bits.to_string());
+    let len = self.history.len();
+    match len % 3 {
+      // Uniformly distributed observations.
+      1 => 0.5,
+      // Deterministic rewards.
+      2 => {
+        if self.history[len - 2] == self.rewarded_action { 1.0 } else { 0.0 }
+      },
+      _ => panic!("Unexpected prediction of action bits"),
+    }
+  }
+}
@@ -25,15 +25,25 @@ extern crate rand;
 
 pub mod agent;
 pub mod bitstring;
+pub mod cli;
+pub mod config;
+pub mod encoding;
 pub mod environment;
+pub mod episode_stats;
 pub mod explorer;
 pub mod logger;
 pub mod predictor;
 pub mod random;
+pub mod serialization;
 pub mod types;
 
 // Unit test modules.
 
 #[cfg(test)] pub mod agent_test;
 #[cfg(test)] pub mod bitstring_test;
+#[cfg(test)] pub mod cli_test;
+#[cfg(test)] pub mod config_test;
+#[cfg(test)] pub mod encoding_test;
+#[cfg(test)] pub mod episode_stats_test;
+#[cfg(test)] pub mod serialization_test;
 
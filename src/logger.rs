@@ -20,11 +20,16 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-/// A logging framework for this application. Right now it just logs to stdout,
-/// but it should eventually support logging AI progress to a file.
+//! A logging framework for this application. Supports logging to stdout and
+//! to files, or to several of those at once.
 
 extern crate log;
 
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
 use log::{LogLevel, LogMetadata, LogRecord};
 
 /// A logger which just logs everything to stdout.
@@ -50,3 +55,65 @@ impl log::Log for StdoutLogger {
     println!("{}:{} - {}", record.level(), record.target(), record.args());
   }
 }
+
+/// A logger which appends everything to a file, one record per line.
+pub struct FileLogger {
+  max_enabled_level: LogLevel,
+  file: Mutex<LineWriter<File>>,
+}
+
+impl FileLogger {
+  /// Creates a logger writing to the supplied path, replacing any existing
+  /// file.
+  pub fn create(
+      path: &Path, max_enabled_level: LogLevel) -> io::Result<FileLogger> {
+    Ok(FileLogger {
+      max_enabled_level: max_enabled_level,
+      file: Mutex::new(LineWriter::new(File::create(path)?)),
+    })
+  }
+}
+
+impl log::Log for FileLogger {
+  fn enabled(&self, metadata: &LogMetadata) -> bool {
+    metadata.level() <= self.max_enabled_level
+  }
+
+  fn log(&self, record: &LogRecord) {
+    if !self.enabled(record.metadata()) {
+      return;
+    }
+    let mut file = self.file.lock().unwrap();
+
+    // There is nowhere to report a failure to log, so we drop the record.
+    let _ = writeln!(
+        file, "{}:{} - {}", record.level(), record.target(), record.args());
+  }
+}
+
+/// A logger which forwards every record to several other loggers.
+pub struct MultiLogger {
+  loggers: Vec<Box<log::Log>>,
+}
+
+impl MultiLogger {
+  pub fn new(loggers: Vec<Box<log::Log>>) -> MultiLogger {
+    MultiLogger {
+      loggers: loggers
+    }
+  }
+}
+
+impl log::Log for MultiLogger {
+  fn enabled(&self, metadata: &LogMetadata) -> bool {
+    self.loggers.iter().any(|logger| logger.enabled(metadata))
+  }
+
+  fn log(&self, record: &LogRecord) {
+    for logger in self.loggers.iter() {
+      if logger.enabled(record.metadata()) {
+        logger.log(record);
+      }
+    }
+  }
+}
@@ -23,43 +23,143 @@
 extern crate ai;
 #[macro_use] extern crate log;
 
-use ai::agent::Agent;
-use ai::environment::environment::Environment;
-use ai::environment::environment::CoinFlip;
-use ai::logger::StdoutLogger;
+use ai::agent::{Agent, PerceptBounds};
+use ai::cli::{self, Options};
+use ai::config;
+use ai::encoding::Encoder;
+use ai::environment::registry;
+use ai::episode_stats::EpisodeStats;
+use ai::explorer::{ExplorationSchedule, MonteCarloParams};
+use ai::logger::{FileLogger, MultiLogger, StdoutLogger};
+use ai::predictor::context_tree::ContextTree;
+use ai::predictor::factored_context_tree::FactoredContextTree;
+use ai::predictor::Predictor;
 use ai::random::RandomImpl;
 
-use log::LogLevelFilter;
-
-// TODO(dinowernli): Replace these with command line flags.
-const CONTEXT_TREE_DEPTH: usize = 4;
-const MAX_LOG_LEVEL: LogLevelFilter = LogLevelFilter::Info;
+use log::LogLevel;
+use std::env;
+use std::path::Path;
+use std::process;
 
 // Without this, cargo test warns that "main" is unused.
 #[cfg_attr(test, allow(dead_code))]
 fn main() {
-  setup_logger();
+  let args: Vec<String> = env::args().skip(1).collect();
+  let options = match cli::parse(&args) {
+    Ok(options) => options,
+    Err(message) => {
+      eprintln!("{}\n\n{}", message, cli::usage());
+      process::exit(2);
+    },
+  };
+  if options.help {
+    println!("{}", cli::usage());
+    return;
+  }
+  setup_logger(&options);
+  info!("Resolved configuration:\n{}", config::format(&options));
 
   // Use one RNG to bootstrap the others so that we only have one
   // magic seed constant.
-  let mut rand = RandomImpl::create(5761567);
+  let mut rand = RandomImpl::create(options.seed);
 
   // Setup the agent and the environment.
-  let mut environment = CoinFlip::new(&mut rand);
-  let mut agent = Agent::create_aixi(
-      environment.num_actions(),
-      CONTEXT_TREE_DEPTH);
+  let mut agent_rand = match options.agent_seed {
+    Some(seed) => RandomImpl::create(seed),
+    None => rand.new_child(),
+  };
+  let mut environment_rand = match options.environment_seed {
+    Some(seed) => RandomImpl::create(seed),
+    None => rand,
+  };
+  let (mut environment, encoder) = registry::create(
+      &options.environment,
+      &options.environment_params,
+      &mut environment_rand).unwrap_or_else(|message| {
+    error!("{}", message);
+    process::exit(2);
+  });
+  let mut agent = match options.resume_file {
+    Some(ref path) => {
+      info!("Resuming agent from {}", path);
+      let agent =
+          Agent::load_from_file(Path::new(path)).unwrap_or_else(|error| {
+        error!("Unable to resume agent from {}: {}", path, error);
+        process::exit(1);
+      });
+
+      // The checkpoint only makes sense for the environment it was trained
+      // on, which must agree on how actions and percepts are encoded.
+      if agent.num_actions() != environment.num_actions() ||
+          agent.encoder() != encoder {
+        error!("Agent in {} does not match environment {}: it expects {} \
+            actions and {:?}, but the environment has {} actions and {:?}",
+            path, options.environment, agent.num_actions(), agent.encoder(),
+            environment.num_actions(), encoder);
+        process::exit(2);
+      }
+      agent
+    },
+    None => {
+      // Only draw from the agent's generator if needed, so that enabling
+      // exploration is the only thing that changes the agent's decisions.
+      let schedule_rand = if options.exploration_rate > 0.0 {
+        Some(agent_rand.new_child())
+      } else {
+        None
+      };
+      let mut agent = Agent::create_aixi(
+          environment.num_actions(),
+          encoder,
+          create_predictor(&options, encoder),
+          options.explorer_type,
+          MonteCarloParams {
+            reward_range:
+                Some((environment.min_reward(), environment.max_reward())),
+            .. options.monte_carlo_params
+          },
+          agent_rand);
+      if let Some(random) = schedule_rand {
+        agent.set_exploration_schedule(ExplorationSchedule::new(
+            options.exploration_rate, options.exploration_decay, random));
+      }
+      agent
+    },
+  };
+  agent.set_percept_bounds(PerceptBounds {
+    min_observation: environment.min_observation(),
+    max_observation: environment.max_observation(),
+    min_reward: environment.min_reward(),
+    max_reward: environment.max_reward(),
+  });
 
   // Let the agent interact with the environment.
-  let n_cycles = 10;
-  info!("Starting simulation with {} cycles", n_cycles);
+  let n_cycles = options.num_cycles;
+  info!("Starting simulation with {} cycles in environment {}",
+      n_cycles, options.environment);
+  let mut episodes = EpisodeStats::new();
   for cycle in 0..n_cycles {
     let action = agent.act();
     environment.update(action);
 
-    let observation = environment.observation();
     let reward = environment.reward();
-    agent.update(observation, reward);
+    episodes.record(reward);
+
+    // The agent sees one continuous stream of percepts, so at the end of an
+    // episode it observes the start of the next one.
+    if environment.is_terminal() {
+      let (episode_return, length) = episodes.end_episode();
+      info!("Episode: {}, return: {:?}, length: {}",
+          episodes.num_episodes(), episode_return, length);
+      environment.reset();
+    }
+
+    let observation = environment.observation();
+    agent.update(observation, reward).unwrap_or_else(|message| {
+      error!("Environment {} produced an invalid percept: {}",
+          options.environment, message);
+      process::exit(1);
+    });
 
     info!("Cycle: {}, [{:?}, {:?}, {:?}]",
         cycle, action, observation, reward);
@@ -68,14 +168,62 @@ fn main() {
   // Report results.
   info!("The average reward after {} rounds is {:?}",
       agent.age(), agent.average_reward());
+  if let (Some(average_return), Some(average_length)) =
+      (episodes.average_return(), episodes.average_length()) {
+    info!("Completed {} episodes with an average return of {:?} and an \
+        average length of {}", episodes.num_episodes(), average_return,
+        average_length);
+  }
+
+  if let Some(ref path) = options.checkpoint_file {
+    match agent.save_to_file(Path::new(path)) {
+      Ok(()) => info!("Saved agent to {}", path),
+      Err(error) => {
+        error!("Unable to save agent to {}: {}", path, error);
+        process::exit(1);
+      },
+    }
+  }
+}
+
+// Returns the predictor described by the options.
+fn create_predictor(options: &Options, encoder: Encoder) -> Box<Predictor> {
+  if options.factored {
+    Box::new(FactoredContextTree::create(
+        encoder.percept_bits(), options.context_tree_depth, options.estimator))
+  } else {
+    Box::new(ContextTree::create(
+        options.context_tree_depth, options.estimator))
+  }
 }
 
 // Installs a logger which handles all log macro invocations or panics.
-fn setup_logger() {
-  log::set_logger(|max_log_level| {
+fn setup_logger(options: &Options) {
+  let max_log_level = options.log_level;
+
+  // The loggers need a level even if logging is disabled entirely, in which
+  // case the macros never reach them.
+  let max_enabled_level =
+      max_log_level.to_log_level().unwrap_or(LogLevel::Error);
+
+  let mut loggers: Vec<Box<log::Log>> = Vec::new();
+  if options.log_stdout {
+    loggers.push(Box::new(StdoutLogger::new(max_enabled_level)));
+  }
+  if let Some(ref path) = options.log_file {
+    match FileLogger::create(Path::new(path), max_enabled_level) {
+      Ok(logger) => loggers.push(Box::new(logger)),
+      Err(error) => {
+        eprintln!("Unable to open log file {}: {}", path, error);
+        process::exit(1);
+      },
+    }
+  }
+
+  log::set_logger(|max| {
     // We're ignoring everything above the max level inside the logger anyway,
     // so here we tell the logging macros that the call can be skipped.
-    max_log_level.set(MAX_LOG_LEVEL);
-    Box::new(StdoutLogger::new(MAX_LOG_LEVEL.to_log_level().unwrap()))
+    max.set(max_log_level);
+    Box::new(MultiLogger::new(loggers))
   }).unwrap();
 }
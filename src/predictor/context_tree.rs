@@ -21,10 +21,16 @@
 // SOFTWARE.
 
 use std::f64;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
 
 use bitstring::Bit;
 use bitstring::Bitstring;
+use predictor::estimator::{Estimator, EstimatorType};
 use predictor::Predictor;
+use random::Random;
+use serialization;
 
 // Open questions:
 // - How to declare some fields final (apparently rejected by rust community).
@@ -39,9 +45,27 @@ use predictor::Predictor;
 /// In order to get a probability for a sequence, all bits in the sequence
 /// must be processed in-order.
 pub struct ContextTree {
-  root: Node,
+  /// Storage for all nodes. The root lives at index ROOT, all other nodes
+  /// are allocated when their context is first seen. Slots of released
+  /// nodes are recycled through free_slots.
+  nodes: Vec<Node>,
+  free_slots: Vec<u32>,
+
+  /// Scratch space holding the indices of the nodes in the current context,
+  /// ordered from the root to the leaf. Kept around to avoid allocations.
+  path: Vec<u32>,
+
   history: Bitstring,
   depth: usize,
+
+  /// Used by every node to estimate the probability of the bits seen in its
+  /// context.
+  estimator: Box<Estimator>,
+  estimator_type: EstimatorType,
+
+  /// For every bit in the history, whether or not the nodes were updated
+  /// when the bit was added. Needed in order to revert correctly.
+  updated: Vec<bool>,
 }
 
 impl Predictor for ContextTree {
@@ -57,8 +81,15 @@ impl Predictor for ContextTree {
   }
 
   fn update(&mut self, bitstring: &Bitstring) {
-    for bit in bitstring.bits() {
-      self.update_bit(*bit);
+    for bit in bitstring.iter() {
+      self.update_bit(bit);
+    }
+  }
+
+  fn update_history(&mut self, bitstring: &Bitstring) {
+    for bit in bitstring.iter() {
+      self.history.push(bit);
+      self.updated.push(false);
     }
   }
 
@@ -84,57 +115,263 @@ impl Predictor for ContextTree {
     let log_conditional_prob = final_log_prob - initial_log_prob;
     return log_conditional_prob.exp2();
   }
+
+  /// Writes the tree to the supplied writer. The format stores the depth,
+  /// the estimator, the history and every allocated node, and reading it
+  /// back with load() produces a tree which makes identical predictions.
+  fn save(&self, writer: &mut Write) -> io::Result<()> {
+    serialization::write_header(writer, MAGIC, VERSION)?;
+    serialization::write_u64(writer, self.depth as u64)?;
+    self.estimator_type.save(writer)?;
+    serialization::write_bitstring(writer, &self.history)?;
+    let updated: Bitstring = self.updated.iter()
+        .map(|&updated| if updated { Bit::One } else { Bit::Zero })
+        .collect();
+    serialization::write_bitstring(writer, &updated)?;
+
+    // Nodes are written in pre-order, each one followed by its zero subtree
+    // and then its one subtree. Released slots are not written.
+    serialization::write_u64(writer, self.size() as u64)?;
+    let mut stack = vec!(ROOT);
+    while let Some(index) = stack.pop() {
+      let node = &self.nodes[index as usize];
+      node.save(writer)?;
+      if node.one_child != NO_CHILD {
+        stack.push(node.one_child);
+      }
+      if node.zero_child != NO_CHILD {
+        stack.push(node.zero_child);
+      }
+    }
+    return Ok(());
+  }
+
+  fn sample_and_update_bit(&mut self, random: &mut Random) -> Bit {
+    // Without enough history, both bits are equally likely.
+    if !self.has_context() {
+      let bit = if random.next_modulo(2) == 1 { Bit::One } else { Bit::Zero };
+      self.update_bit(bit);
+      return bit;
+    }
+
+    // Optimistically add a one, which leaves us with nothing left to do if
+    // the sampled bit turns out to be a one.
+    let initial_log_prob = self.log_block_prob();
+    self.update_bit(Bit::One);
+    let prob_one = (self.log_block_prob() - initial_log_prob).exp2();
+    if random.next_f64() < prob_one {
+      return Bit::One;
+    }
+
+    self.revert_last();
+    self.update_bit(Bit::Zero);
+    return Bit::Zero;
+  }
 }
 
 impl ContextTree {
   /// The depth of the tree is the distance between leaves and the root.
   /// For a given depth d, the tree will compute a mixture model of all
-  /// Markov chains of degree at most d.
-  pub fn create(depth: usize) -> Self {
+  /// Markov chains of degree at most d. Nodes are only allocated once their
+  /// context has been seen, so large depths are affordable.
+  pub fn create(depth: usize, estimator: EstimatorType) -> Self {
     ContextTree {
-      root: Node::create_root(depth, 0),
+      nodes: vec!(Node::new()),
+      free_slots: Vec::new(),
+      path: Vec::with_capacity(depth + 1),
       history: Bitstring::new(),
       depth: depth,
+      estimator: estimator.create(),
+      estimator_type: estimator,
+      updated: Vec::new(),
     }
   }
 
-  /// Returns the total number of nodes in the tree.
+  /// Reads a tree previously written by save().
+  pub fn load(reader: &mut Read) -> io::Result<ContextTree> {
+    serialization::read_header(reader, MAGIC, VERSION)?;
+    let depth = serialization::read_u64(reader)?;
+    if depth > MAX_LOADED_DEPTH as u64 {
+      return Err(serialization::invalid_data(&format!(
+          "Depth {} exceeds the maximum of {}", depth, MAX_LOADED_DEPTH)));
+    }
+    let depth = depth as usize;
+    let estimator_type = EstimatorType::load(reader)?;
+    let history = serialization::read_bitstring(reader)?;
+    let updated = serialization::read_bitstring(reader)?;
+    if updated.len() != history.len() {
+      return Err(serialization::invalid_data(
+          "Update flags do not match the history"));
+    }
+
+    let mut tree = ContextTree::create(depth, estimator_type);
+    tree.history = history;
+    tree.updated = updated.iter().map(|bit| bit == Bit::One).collect();
+
+    let num_nodes = serialization::read_u64(reader)?;
+    let (root, children) = Node::load(reader)?;
+    tree.nodes[ROOT as usize] = root;
+
+    // Each entry holds a parent, the bit leading to the missing child and
+    // the depth of that child. Zero children are popped first.
+    let mut stack = Vec::new();
+    push_children(&mut stack, ROOT, 0, children);
+    while let Some((parent, bit, depth)) = stack.pop() {
+      if depth > tree.depth || tree.nodes.len() as u64 >= num_nodes {
+        return Err(serialization::invalid_data("Malformed node structure"));
+      }
+      let (node, children) = Node::load(reader)?;
+      let index = tree.nodes.len() as u32;
+      tree.nodes.push(node);
+      *tree.nodes[parent as usize].mut_child(bit) = index;
+      push_children(&mut stack, index, depth, children);
+    }
+
+    if tree.nodes.len() as u64 != num_nodes {
+      return Err(serialization::invalid_data("Malformed node structure"));
+    }
+    return Ok(tree);
+  }
+
+  /// Writes the tree to a file, replacing any existing contents.
+  pub fn save_to_file(&self, path: &Path) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    self.save(&mut writer)?;
+    writer.flush()
+  }
+
+  /// Reads a tree from a file previously written by save_to_file().
+  pub fn load_from_file(path: &Path) -> io::Result<ContextTree> {
+    let mut reader = BufReader::new(File::open(path)?);
+    ContextTree::load(&mut reader)
+  }
+
+  /// Returns the number of nodes currently allocated in the tree.
   pub fn size(&self) -> usize {
-    self.root.size()
+    self.nodes.len() - self.free_slots.len()
   }
 
   /// Adds a single bit to the history seen by this tree, updating all the
   /// involved nodes if necessary.
   fn update_bit(&mut self, bit: Bit) {
-    self.for_node_in_context(&|node: &mut Node| {
-      node.update(bit);
-    });
+    if self.has_context() {
+      self.compute_path();
+      for i in (0..self.path.len()).rev() {
+        let index = self.path[i] as usize;
+        self.nodes[index].update(bit, &*self.estimator);
+        self.update_weighted_prob(index, i == self.depth);
+      }
+    }
     self.history.push(bit);
+    self.updated.push(true);
   }
 
   /// Removes a single bit from the history seen by this tree, updating all the
-  /// involved nodes if necessary.
+  /// involved nodes if necessary. Nodes which no longer have any counts are
+  /// released.
   fn revert_last(&mut self) {
     let bit = self.history.pop();
-    self.for_node_in_context(&|node: &mut Node| {
-      node.revert(bit);
-    });
+    if !self.updated.pop().unwrap() || !self.has_context() {
+      return;
+    }
+
+    self.compute_path();
+    for i in (0..self.path.len()).rev() {
+      let index = self.path[i] as usize;
+      self.nodes[index].revert(bit, &*self.estimator);
+
+      // Our child on the path has already been reverted.
+      if i < self.depth {
+        let child = self.path[i + 1];
+        if self.nodes[child as usize].is_empty() {
+          let context_bit = self.context_bit(i);
+          *self.nodes[index].mut_child(context_bit) = NO_CHILD;
+          self.free_slots.push(child);
+        }
+      }
+      self.update_weighted_prob(index, i == self.depth);
+    }
   }
 
-  /// Returns log2 of the estimated probability of the current history.
-  fn log_block_prob(&mut self) -> f64 {
-    return self.root.log_weighted_prob();
+  /// Populates self.path with the indices of the nodes along the current
+  /// context, allocating missing nodes. Requires that the tree has context.
+  fn compute_path(&mut self) {
+    self.path.clear();
+    self.path.push(ROOT);
+
+    let mut index = ROOT;
+    for depth in 0..self.depth {
+      let context_bit = self.context_bit(depth);
+      let mut child = *self.nodes[index as usize].mut_child(context_bit);
+      if child == NO_CHILD {
+        child = self.allocate_node();
+        *self.nodes[index as usize].mut_child(context_bit) = child;
+      }
+      self.path.push(child);
+      index = child;
+    }
   }
 
-  /// Executes the provided closure on the current context, i.e., follows the
-  /// current history down to a leaf and executes the closure on every node
-  /// from the leaf to the root. Does nothing if the current history is not
-  /// long enough to reach a leaf.
-  fn for_node_in_context<F: Fn(&mut Node)>(&mut self, closure: &F) {
-    if !self.has_context() {
+  /// Returns the bit of the history which determines the child taken at the
+  /// supplied depth.
+  fn context_bit(&self, depth: usize) -> Bit {
+    self.history.bit(self.history.len() - depth - 1)
+  }
+
+  /// Returns the index of a fresh node, reusing a released slot if possible.
+  fn allocate_node(&mut self) -> u32 {
+    match self.free_slots.pop() {
+      Some(index) => {
+        self.nodes[index as usize] = Node::new();
+        index
+      },
+      None => {
+        self.nodes.push(Node::new());
+        (self.nodes.len() - 1) as u32
+      },
+    }
+  }
+
+  /// Updates the value of log_weighted_prob for the node at the supplied
+  /// index. Assumes that log_estimated_prob as well as all values in all
+  /// children (if any) are up-to-date.
+  fn update_weighted_prob(&mut self, index: usize, is_leaf: bool) {
+    if is_leaf {
+      let node = &mut self.nodes[index];
+      node.log_weighted_prob = node.log_estimated_prob;
       return;
     }
-    self.root.execute(closure, &self.history, 0);
+
+    let log_weighted_probs =
+        self.child_log_weighted_prob(self.nodes[index].zero_child) +
+        self.child_log_weighted_prob(self.nodes[index].one_child);
+
+    let node = &mut self.nodes[index];
+    let exponent = log_weighted_probs - node.log_estimated_prob;
+    let power = exponent.exp2();
+
+    // The recursive term should be log2(1 + 2^exponent). If the exponent is
+    // large enough, 2^exponent is inf. In this case, we approximate
+    // log2(1 + 2^exponent) by log2(2^exponent) = exponent.
+    let recursive_term =
+        if power == f64::INFINITY { exponent } else { (power + 1.0).log2() };
+
+    node.log_weighted_prob = node.log_estimated_prob + recursive_term - 1.0;
+  }
+
+  /// Returns the log weighted probability of a child. A missing child has
+  /// not seen any bits, so the probability of its (empty) sequence is 1.
+  fn child_log_weighted_prob(&self, child: u32) -> f64 {
+    if child == NO_CHILD {
+      0.0
+    } else {
+      self.nodes[child as usize].log_weighted_prob
+    }
+  }
+
+  /// Returns log2 of the estimated probability of the current history.
+  fn log_block_prob(&mut self) -> f64 {
+    return self.nodes[ROOT as usize].log_weighted_prob;
   }
 
   /// Inidicates whether this tree "has context", i.e., whether or not the
@@ -142,23 +379,39 @@ impl ContextTree {
   fn has_context(&self) -> bool { self.history_size() >= self.depth }
 }
 
+/// Identifies the serialized format of a context tree.
+pub const MAGIC: &[u8; 4] = b"CTWT";
+const VERSION: u32 = 1;
+
+/// The largest depth accepted when loading a tree. Guards against allocating
+/// for a corrupt depth; real trees are far shallower.
+const MAX_LOADED_DEPTH: usize = 1 << 16;
+
+/// The index of the root node.
+const ROOT: u32 = 0;
+
+/// Marks a missing child. The root is never anyone's child, so its index is
+/// free to serve this purpose.
+const NO_CHILD: u32 = ROOT;
+
 /// One node in the context tree. Maintains a count of the number
 /// of zeroes and ones seen and provides access to partial results
 /// of the overall probability estimates computed in the context
 /// tree.
 struct Node {
-  /// Children.
-  zero_child: Option<Box<Node>>,
-  one_child: Option<Box<Node>>,
+  /// Indices of the children, or NO_CHILD if the child's context has not
+  /// been seen. A missing child is equivalent to a child which has not seen
+  /// any bits.
+  zero_child: u32,
+  one_child: u32,
 
   /// Frequencies.
   zeroes: u64,
   ones: u64,
 
   /// The log2 of the probability of a sequence with self.zeroes zeroes
-  /// and self.ones one. Computed using the Krichevsky Trofimov
-  /// estimator algorithm.
-  log_kt_prob: f64,
+  /// and self.ones one, as computed by the tree's estimator.
+  log_estimated_prob: f64,
 
   /// The log2 of the weighted block probability of the current sequence.
   /// Funtion of self.zeroes and self.ones and the values recursively
@@ -167,82 +420,37 @@ struct Node {
 }
 
 impl Node {
-  /// Creates a node and all its children recursively until the provided
-  /// depth is reached.
-  pub fn create_root(target_depth: usize, current_depth: usize) -> Self {
-    if target_depth == current_depth {
-      return Node::new(None, None);  // Leaf.
-    } else {
-      return Node::new(
-          Some(Box::new(Node::create_root(target_depth, current_depth + 1))),
-          Some(Box::new(Node::create_root(target_depth, current_depth + 1))));
-    }
-  }
-
-  fn new(zero_child: Option<Box<Node>>, one_child: Option<Box<Node>>) -> Self {
-    // Context trees are complete trees (either both children or no children).
-    assert!(zero_child.is_none() == one_child.is_none());
-
+  fn new() -> Self {
     return Node {
-      zero_child: zero_child,
-      one_child: one_child,
+      zero_child: NO_CHILD,
+      one_child: NO_CHILD,
       zeroes: 0,
       ones: 0,
-      log_kt_prob: 0.0,
+      log_estimated_prob: 0.0,
       log_weighted_prob: 0.0,
     };
   }
 
-  pub fn log_weighted_prob(&self) -> f64 {
-    self.log_weighted_prob
-  }
-
-  /// Executes the given closure on the appropriate child (if any) and then on
-  /// itself.
-  pub fn execute<F: Fn(&mut Node)>(
-      &mut self,
-      closure: &F,
-      history: &Bitstring,
-      depth: usize) {
-    if !self.is_leaf() {
-      let bit = history.bit(history.len() - depth - 1);
-      self.mut_child(bit).execute(closure, history, depth + 1);
-    }
-    closure(self);
-  }
-
-  /// Updates the state of the node based on the value of the new bit.
-  /// Assumes that child nodes (if any) have already been updated.
-  pub fn update(&mut self, bit: Bit) {
+  /// Updates the frequencies and log_estimated_prob based on the value of
+  /// the new bit. The weighted probability is maintained by the tree.
+  fn update(&mut self, bit: Bit, estimator: &Estimator) {
+    self.log_estimated_prob = self.log_estimated_prob +
+        estimator.log_conditional_prob(self.zeroes, self.ones, bit);
     *self.mut_frequency(bit) += 1;
-
-    // Old value + 0.5 is the same as new value - 0.5.
-    let first_summand = (*self.mut_frequency(bit) as f64 - 0.5).log2();
-    let norm_summand = ((self.zeroes + self.ones) as f64).log2();
-
-    self.log_kt_prob = first_summand - norm_summand + self.log_kt_prob;
-    self.update_weighted_prob();
   }
 
   /// Corresponds to undoing one update for the specified bit value.
-  /// Assumes that child nodes (if any) have already been reverted.
-  pub fn revert(&mut self, bit: Bit) {
+  fn revert(&mut self, bit: Bit, estimator: &Estimator) {
     *self.mut_frequency(bit) -= 1;
-
-    let first_summand = (*self.mut_frequency(bit) as f64 + 0.5).log2();
-    let norm_summand = ((self.zeroes + self.ones + 1) as f64).log2();
-
-    self.log_kt_prob = self.log_kt_prob - first_summand + norm_summand;
-    self.update_weighted_prob();
+    self.log_estimated_prob = self.log_estimated_prob -
+        estimator.log_conditional_prob(self.zeroes, self.ones, bit);
   }
 
-  /// Returns the child corresponding to the supplied bit. Must not be called
-  /// on leaf nodes.
-  fn mut_child(&mut self, bit: Bit) -> &mut Node {
-    assert!(!self.is_leaf());
+  /// Returns the child index corresponding to the supplied bit.
+  fn mut_child(&mut self, bit: Bit) -> &mut u32 {
     match bit {
-      Bit::Zero => self.zero_child.as_mut().unwrap(),
-      Bit::One => self.one_child.as_mut().unwrap(),
+      Bit::Zero => &mut self.zero_child,
+      Bit::One => &mut self.one_child,
     }
   }
 
@@ -254,42 +462,51 @@ impl Node {
     }
   }
 
-  /// Updates the value of log_weighted_prob. Assumes that log_kt_prob
-  /// as well as all values in all children (if any) are up-to-date.
-  fn update_weighted_prob(&mut self) {
-    if self.is_leaf() {
-      self.log_weighted_prob = self.log_kt_prob;
-      return;
-    }
-
-    let log_weighted_probs =
-        self.zero_child.as_ref().unwrap().log_weighted_prob() +
-        self.one_child.as_ref().unwrap().log_weighted_prob();
-    let exponent = log_weighted_probs - self.log_kt_prob;
-    let power = exponent.exp2();
-
-    // The recursive term should be log2(1 + 2^exponent). If the exponent is
-    // large enough, 2^exponent is inf. In this case, we approximate
-    // log2(1 + 2^exponent) by log2(2^exponent) = exponent.
-    let recursive_term =
-        if power == f64::INFINITY { exponent } else { (power + 1.0).log2() };
+  /// Indicates whether this node has not seen any bits.
+  fn is_empty(&self) -> bool {
+    self.zeroes == 0 && self.ones == 0
+  }
 
-    self.log_weighted_prob = self.log_kt_prob + recursive_term - 1.0;
+  /// Writes the node's statistics, preceded by flags indicating which
+  /// children are present.
+  fn save(&self, writer: &mut Write) -> io::Result<()> {
+    let mut flags = 0;
+    if self.zero_child != NO_CHILD { flags |= ZERO_CHILD_FLAG; }
+    if self.one_child != NO_CHILD { flags |= ONE_CHILD_FLAG; }
+    serialization::write_u8(writer, flags)?;
+    serialization::write_u64(writer, self.zeroes)?;
+    serialization::write_u64(writer, self.ones)?;
+    serialization::write_f64(writer, self.log_estimated_prob)?;
+    serialization::write_f64(writer, self.log_weighted_prob)
   }
 
-  /// Returns the number of nodes in the subtree rooted at this node.
-  fn size(&self) -> usize {
-    1 + match self.zero_child {
-      None => 0,
-      Some(ref node) => node.size(),
-    } + match self.one_child {
-      None => 0,
-      Some(ref node) => node.size(),
+  /// Reads a node written by save(). Returns the node, without children, and
+  /// the flags indicating which children follow it.
+  fn load(reader: &mut Read) -> io::Result<(Node, u8)> {
+    let flags = serialization::read_u8(reader)?;
+    if flags & !(ZERO_CHILD_FLAG | ONE_CHILD_FLAG) != 0 {
+      return Err(serialization::invalid_data("Invalid node flags"));
     }
+    let mut node = Node::new();
+    node.zeroes = serialization::read_u64(reader)?;
+    node.ones = serialization::read_u64(reader)?;
+    node.log_estimated_prob = serialization::read_f64(reader)?;
+    node.log_weighted_prob = serialization::read_f64(reader)?;
+    return Ok((node, flags));
   }
+}
+
+const ZERO_CHILD_FLAG: u8 = 1;
+const ONE_CHILD_FLAG: u8 = 2;
 
-  fn is_leaf(&self) -> bool {
-    // By construction we have either both children or none.
-    return self.zero_child.is_none();
+/// Schedules the children indicated by flags to be read, such that the zero
+/// child is read first.
+fn push_children(
+    stack: &mut Vec<(u32, Bit, usize)>, parent: u32, depth: usize, flags: u8) {
+  if flags & ONE_CHILD_FLAG != 0 {
+    stack.push((parent, Bit::One, depth + 1));
+  }
+  if flags & ZERO_CHILD_FLAG != 0 {
+    stack.push((parent, Bit::Zero, depth + 1));
   }
 }
@@ -20,33 +20,77 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use bitstring::Bitstring;
+use std::env;
+use std::fs;
+use std::io::{Cursor, ErrorKind};
+
+use bitstring::{Bit, Bitstring};
 use predictor::context_tree::ContextTree;
+use predictor::estimator::EstimatorType;
 use predictor::Predictor;
+use predictor::test_util::assert_almost_eq;
+use random::RandomImpl;
 
 const EPS: f64 = 0.0000001;
+const KT: EstimatorType = EstimatorType::KrichevskyTrofimov;
 
 #[test]
 fn size() {
-  let tree = ContextTree::create(3);
-  assert_eq!(15, tree.size());  // 2^(n+1) - 1.
+  let mut tree = ContextTree::create(3, KT);
+  assert_eq!(1, tree.size());  // Only the root is allocated up front.
+
+  // The first update needs three bits of context and allocates one path.
+  tree.update(&Bitstring::create_from_string("0100"));
+  assert_eq!(4, tree.size());
+
+  // The next update follows a path which shares the first two nodes.
+  tree.update(&Bitstring::create_from_string("1"));
+  assert_eq!(6, tree.size());
+}
+
+#[test]
+fn revert_releases_nodes() {
+  let mut tree = ContextTree::create(3, KT);
+  tree.update(&Bitstring::create_from_string("0100"));
+  let initial_size = tree.size();
+
+  tree.update(&Bitstring::create_from_string("10110"));
+  tree.revert_to_history_size(4);
+  assert_eq!(initial_size, tree.size());
+
+  tree.revert_to_history_size(0);
+  assert_eq!(1, tree.size());
+}
+
+#[test]
+fn large_depth() {
+  let mut tree = ContextTree::create(200, KT);
+  let mut history = String::new();
+  for i in 0..300 {
+    history.push(if i % 3 == 0 { '1' } else { '0' });
+  }
+  tree.update(&Bitstring::create_from_string(&history));
+
+  // Every update allocates at most one new path of 200 nodes.
+  assert!(tree.size() <= 1 + 100 * 200);
+  assert!(tree.predict(&Bitstring::create_from_string("100")) > 0.5);
 }
 
 #[test]
 fn empty_size() {
-  let tree = ContextTree::create(0);
+  let tree = ContextTree::create(0, KT);
   assert_eq!(1, tree.size());
 }
 
 #[test]
 fn predict_empty() {
-  let mut tree = ContextTree::create(0);
+  let mut tree = ContextTree::create(0, KT);
   assert_almost_eq(1.0, tree.predict(&Bitstring::create_empty()), EPS);
 }
 
 #[test]
 fn predict_uniform() {
-  let mut tree = ContextTree::create(3);
+  let mut tree = ContextTree::create(3, KT);
   let prob = tree.predict(&Bitstring::create_from_string("01"));
 
   // Bitstring of length 2, not enough history, so uniform distribution.
@@ -55,7 +99,7 @@ fn predict_uniform() {
 
 #[test]
 fn predict_with_history() {
-  let mut tree = ContextTree::create(3);
+  let mut tree = ContextTree::create(3, KT);
   let bits = Bitstring::create_from_string("100");
   let history = Bitstring::create_from_string("10011110");
 
@@ -68,9 +112,41 @@ fn predict_with_history() {
   assert_almost_eq(0.050951086956, tree.predict(&bits), EPS);
 }
 
+#[test]
+fn revert_restores_state_with_other_estimators() {
+  let estimators = [
+    EstimatorType::Laplace,
+    EstimatorType::Dirichlet(0.1),
+    EstimatorType::ZeroRedundancy,
+  ];
+  for estimator in estimators.iter() {
+    let mut tree = ContextTree::create(4, *estimator);
+    tree.update(&Bitstring::create_from_string("0100101000011"));
+    let bits = Bitstring::create_from_string("0110");
+    let initial_prob = tree.predict(&bits);
+
+    tree.update(&Bitstring::create_from_string("1111010"));
+    tree.revert_to_history_size(13);
+    assert_almost_eq(initial_prob, tree.predict(&bits), EPS);
+  }
+}
+
+#[test]
+fn zero_redundancy_learns_deterministic_context() {
+  let history = Bitstring::create_from_string("0101010101010101");
+  let next = Bitstring::create_from_string("0");
+
+  let mut kt = ContextTree::create(2, KT);
+  kt.update(&history);
+  let mut zr = ContextTree::create(2, EstimatorType::ZeroRedundancy);
+  zr.update(&history);
+
+  assert!(zr.predict(&next) > kt.predict(&next));
+}
+
 #[test]
 fn revert_restores_size() {
-  let mut tree = ContextTree::create(7);
+  let mut tree = ContextTree::create(7, KT);
   tree.update(&Bitstring::create_from_string("0100101001010000"));
   tree.revert_to_history_size(5);
   assert_eq!(5, tree.history_size());
@@ -79,7 +155,7 @@ fn revert_restores_size() {
 #[test]
 fn revert_restores_state() {
   // Setup a tree with some arbitrary history which is long enough.
-  let mut tree = ContextTree::create(7);
+  let mut tree = ContextTree::create(7, KT);
   tree.update(&Bitstring::create_from_string("01001010001001111"));
   let initial_size = tree.history_size();
 
@@ -100,25 +176,176 @@ fn revert_restores_state() {
 
 #[test]
 fn noop_revert_is_valid() {
-  let mut tree = ContextTree::create(3);
+  let mut tree = ContextTree::create(3, KT);
   tree.update(&Bitstring::create_from_string("00010"));
   let history_size = tree.history_size();
   tree.revert_to_history_size(history_size);  // Shouldn't crash.
 }
 
+#[test]
+fn history_only_update() {
+  // A tree of depth 0 is a single estimator without any context.
+  let mut learned = ContextTree::create(0, KT);
+  learned.update(&Bitstring::create_from_string("1111"));
+  let mut conditioned = ContextTree::create(0, KT);
+  conditioned.update_history(&Bitstring::create_from_string("1111"));
+
+  let bits = Bitstring::create_from_string("1");
+  assert_eq!(4, conditioned.history_size());
+  assert_almost_eq(0.9, learned.predict(&bits), EPS);
+  assert_almost_eq(0.5, conditioned.predict(&bits), EPS);
+}
+
+#[test]
+fn revert_history_only_update() {
+  let mut tree = ContextTree::create(3, KT);
+  tree.update(&Bitstring::create_from_string("0110100"));
+  let initial_size = tree.history_size();
+  let bits = Bitstring::create_from_string("01");
+  let initial_prob = tree.predict(&bits);
+
+  // Interleave bits which are learned from with bits which are not.
+  tree.update_history(&Bitstring::create_from_string("11"));
+  tree.update(&Bitstring::create_from_string("010"));
+  tree.update_history(&Bitstring::create_from_string("0"));
+  tree.update(&Bitstring::create_from_string("1"));
+  tree.revert_to_history_size(initial_size);
+
+  assert_eq!(initial_size, tree.history_size());
+  assert_almost_eq(initial_prob, tree.predict(&bits), EPS);
+}
+
+#[test]
+fn sample_follows_history() {
+  let mut tree = ContextTree::create(2, KT);
+  tree.update(&Bitstring::create_from_string("1111111111111111111111111"));
+
+  let mut random = RandomImpl::create(5678);
+  let samples = tree.sample_bits(&mut random, 50);
+  let ones = (0..samples.len()).filter(|i| samples.bit(*i) == Bit::One);
+  assert!(ones.count() > 45);
+}
+
+#[test]
+fn sample_restores_history() {
+  let mut tree = ContextTree::create(3, KT);
+  tree.update(&Bitstring::create_from_string("0110100"));
+  let bits = Bitstring::create_from_string("101");
+  let initial_prob = tree.predict(&bits);
+
+  let mut random = RandomImpl::create(5678);
+  tree.sample_bit(&mut random);
+  tree.sample_bits(&mut random, 10);
+
+  assert_eq!(7, tree.history_size());
+  assert_almost_eq(initial_prob, tree.predict(&bits), EPS);
+}
+
+#[test]
+fn sample_and_update() {
+  let mut tree = ContextTree::create(3, KT);
+  tree.update(&Bitstring::create_from_string("0110100"));
+
+  let mut random = RandomImpl::create(5678);
+  let bit = tree.sample_and_update_bit(&mut random);
+  let bits = tree.sample_and_update_bits(&mut random, 10);
+  assert_eq!(10, bits.len());
+  assert_eq!(18, tree.history_size());
+
+  // Reverting removes exactly the sampled bits.
+  tree.revert_to_history_size(8);
+  let mut expected = ContextTree::create(3, KT);
+  expected.update(&Bitstring::create_from_string("0110100"));
+  let mut last = Bitstring::new();
+  last.push(bit);
+  expected.update(&last);
+  let next = Bitstring::create_from_string("10");
+  assert_almost_eq(expected.predict(&next), tree.predict(&next), EPS);
+}
+
+#[test]
+fn save_and_load() {
+  let estimators = [KT, EstimatorType::Dirichlet(0.25)];
+  for estimator in estimators.iter() {
+    let mut tree = ContextTree::create(5, *estimator);
+    tree.update(&Bitstring::create_from_string("0110101110010111011"));
+    tree.update_history(&Bitstring::create_from_string("10"));
+    tree.update(&Bitstring::create_from_string("1"));
+
+    let mut bytes = Vec::new();
+    tree.save(&mut bytes).unwrap();
+    let mut loaded = ContextTree::load(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(tree.size(), loaded.size());
+    assert_eq!(tree.history_size(), loaded.history_size());
+
+    // Predictions must match exactly, including after further updates and
+    // after reverting past the point at which the tree was saved.
+    let next = Bitstring::create_from_string("011");
+    assert_eq!(tree.predict(&next), loaded.predict(&next));
+    tree.update(&next);
+    loaded.update(&next);
+    assert_eq!(tree.predict(&next), loaded.predict(&next));
+    tree.revert_to_history_size(10);
+    loaded.revert_to_history_size(10);
+    assert_eq!(tree.size(), loaded.size());
+    assert_eq!(tree.predict(&next), loaded.predict(&next));
+  }
+}
+
+#[test]
+fn save_and_load_file() {
+  let mut tree = ContextTree::create(3, KT);
+  tree.update(&Bitstring::create_from_string("01101011"));
+
+  let path = env::temp_dir().join("context_tree_test_save_and_load_file.ctw");
+  tree.save_to_file(&path).unwrap();
+  let mut loaded = ContextTree::load_from_file(&path).unwrap();
+  fs::remove_file(&path).unwrap();
+
+  let next = Bitstring::create_from_string("1");
+  assert_eq!(tree.predict(&next), loaded.predict(&next));
+}
+
+#[test]
+fn load_rejects_invalid_data() {
+  let mut tree = ContextTree::create(3, KT);
+  tree.update(&Bitstring::create_from_string("01101011"));
+  let mut bytes = Vec::new();
+  tree.save(&mut bytes).unwrap();
+
+  let mut bad_magic = bytes.clone();
+  bad_magic[0] = b'X';
+  let error = ContextTree::load(&mut Cursor::new(bad_magic)).err().unwrap();
+  assert_eq!(ErrorKind::InvalidData, error.kind());
+
+  let mut bad_version = bytes.clone();
+  bad_version[4] = 7;
+  let error = ContextTree::load(&mut Cursor::new(bad_version)).err().unwrap();
+  assert_eq!(ErrorKind::InvalidData, error.kind());
+
+  let truncated = bytes[..bytes.len() - 1].to_vec();
+  let error = ContextTree::load(&mut Cursor::new(truncated)).err().unwrap();
+  assert_eq!(ErrorKind::UnexpectedEof, error.kind());
+}
+
+#[test]
+fn load_rejects_corrupt_depth() {
+  let mut bytes = Vec::new();
+  ContextTree::create(3, KT).save(&mut bytes).unwrap();
+
+  // The depth follows the magic and the version. Loading must fail before
+  // allocating anything for it.
+  bytes[8..16].copy_from_slice(&(1u64 << 40).to_le_bytes());
+  let error = ContextTree::load(&mut Cursor::new(bytes)).err().unwrap();
+  assert_eq!(ErrorKind::InvalidData, error.kind());
+}
+
 #[test]
 #[should_panic]
 fn invalid_revert() {
-  let mut tree = ContextTree::create(0);
+  let mut tree = ContextTree::create(0, KT);
   assert_eq!(0, tree.history_size());
 
   // Attempt to revert to something greater than the history size.
   tree.revert_to_history_size(17);
 }
-
-fn assert_almost_eq(expected: f64, actual: f64, tol: f64) {
-  let diff = expected - actual;
-  let message = format!(
-      "expected {} but got {}, diff = {}", expected, actual, diff);
-  assert!(-tol < diff && diff < tol, message);
-}
@@ -0,0 +1,207 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::f64;
+use std::io::{self, Read, Write};
+
+use bitstring::Bit;
+use serialization;
+
+/// Estimates the probability of the next bit in a sequence based only on the
+/// number of zeroes and ones seen so far. Every node of a context tree uses
+/// an estimator to compute the probability of the bits seen in its context.
+pub trait Estimator {
+  /// Returns log2 of the probability that the next bit is "bit", given that
+  /// "zeroes" zeroes and "ones" ones have been seen so far.
+  fn log_conditional_prob(&self, zeroes: u64, ones: u64, bit: Bit) -> f64;
+}
+
+/// The estimators available to context trees.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EstimatorType {
+  KrichevskyTrofimov,
+  Laplace,
+  Dirichlet(f64),
+  ZeroRedundancy,
+}
+
+impl EstimatorType {
+  pub fn create(&self) -> Box<Estimator> {
+    match *self {
+      EstimatorType::KrichevskyTrofimov => Box::new(KrichevskyTrofimov),
+      EstimatorType::Laplace => Box::new(Laplace),
+      EstimatorType::Dirichlet(alpha) => Box::new(Dirichlet::new(alpha)),
+      EstimatorType::ZeroRedundancy => Box::new(ZeroRedundancy),
+    }
+  }
+
+  /// Writes a tag identifying the estimator, followed by its parameter.
+  pub fn save(&self, writer: &mut Write) -> io::Result<()> {
+    let (tag, alpha) = match *self {
+      EstimatorType::KrichevskyTrofimov => (0, 0.0),
+      EstimatorType::Laplace => (1, 0.0),
+      EstimatorType::Dirichlet(alpha) => (2, alpha),
+      EstimatorType::ZeroRedundancy => (3, 0.0),
+    };
+    serialization::write_u8(writer, tag)?;
+    serialization::write_f64(writer, alpha)
+  }
+
+  pub fn load(reader: &mut Read) -> io::Result<EstimatorType> {
+    let tag = serialization::read_u8(reader)?;
+    let alpha = serialization::read_f64(reader)?;
+    match tag {
+      0 => Ok(EstimatorType::KrichevskyTrofimov),
+      1 => Ok(EstimatorType::Laplace),
+      2 if alpha > 0.0 => Ok(EstimatorType::Dirichlet(alpha)),
+      2 => Err(serialization::invalid_data("Dirichlet alpha must be positive")),
+      3 => Ok(EstimatorType::ZeroRedundancy),
+      _ => Err(serialization::invalid_data(
+          &format!("Unknown estimator tag {}", tag))),
+    }
+  }
+}
+
+/// The Krichevsky-Trofimov estimator, i.e., a Dirichlet estimator with
+/// alpha = 0.5. The default choice for context tree weighting.
+pub struct KrichevskyTrofimov;
+
+impl Estimator for KrichevskyTrofimov {
+  fn log_conditional_prob(&self, zeroes: u64, ones: u64, bit: Bit) -> f64 {
+    log_dirichlet_prob(zeroes, ones, bit, 0.5)
+  }
+}
+
+/// The Laplace estimator, i.e., a Dirichlet estimator with alpha = 1.
+pub struct Laplace;
+
+impl Estimator for Laplace {
+  fn log_conditional_prob(&self, zeroes: u64, ones: u64, bit: Bit) -> f64 {
+    log_dirichlet_prob(zeroes, ones, bit, 1.0)
+  }
+}
+
+/// Estimator which adds alpha pseudo-counts to both frequencies. Smaller
+/// values of alpha make the estimate follow the observed counts more closely.
+pub struct Dirichlet {
+  alpha: f64,
+}
+
+impl Dirichlet {
+  pub fn new(alpha: f64) -> Self {
+    assert!(alpha > 0.0, "Dirichlet estimator requires alpha > 0");
+    Dirichlet {
+      alpha: alpha,
+    }
+  }
+}
+
+impl Estimator for Dirichlet {
+  fn log_conditional_prob(&self, zeroes: u64, ones: u64, bit: Bit) -> f64 {
+    log_dirichlet_prob(zeroes, ones, bit, self.alpha)
+  }
+}
+
+/// The zero-redundancy estimator from "Context Tree Weighting: Basic
+/// Properties". Mixes the Krichevsky-Trofimov estimate with the hypotheses
+/// that the sequence is all zeroes or all ones. Learns deterministic
+/// contexts much faster than the Krichevsky-Trofimov estimator:
+///     P(a, b) = P_kt(a, b) / 2 + [b = 0] / 4 + [a = 0] / 4
+pub struct ZeroRedundancy;
+
+impl ZeroRedundancy {
+  /// Returns the probability of a sequence with the supplied frequencies.
+  /// Only used if at least one of the frequencies is small, since otherwise
+  /// the result can underflow.
+  fn prob(zeroes: u64, ones: u64) -> f64 {
+    let mut result = 0.5 * log_kt_block_prob(zeroes, ones).exp();
+    if zeroes == 0 {
+      result = result + 0.25;
+    }
+    if ones == 0 {
+      result = result + 0.25;
+    }
+    return result;
+  }
+}
+
+impl Estimator for ZeroRedundancy {
+  fn log_conditional_prob(&self, zeroes: u64, ones: u64, bit: Bit) -> f64 {
+    // Once both bits have been seen, the deterministic hypotheses no longer
+    // contribute and the ratio is the same as for Krichevsky-Trofimov.
+    if zeroes > 0 && ones > 0 {
+      return log_dirichlet_prob(zeroes, ones, bit, 0.5);
+    }
+
+    let before = ZeroRedundancy::prob(zeroes, ones);
+    let after = match bit {
+      Bit::Zero => ZeroRedundancy::prob(zeroes + 1, ones),
+      Bit::One => ZeroRedundancy::prob(zeroes, ones + 1),
+    };
+    return (after / before).log2();
+  }
+}
+
+/// Returns log2 of (count + alpha) / (zeroes + ones + 2 * alpha), where count
+/// is the frequency of the supplied bit.
+fn log_dirichlet_prob(zeroes: u64, ones: u64, bit: Bit, alpha: f64) -> f64 {
+  let count = match bit {
+    Bit::Zero => zeroes,
+    Bit::One => ones,
+  };
+  let total = (zeroes + ones) as f64 + 2.0 * alpha;
+  return (count as f64 + alpha).log2() - total.log2();
+}
+
+/// Returns the natural log of the Krichevsky-Trofimov probability of a
+/// sequence with the supplied frequencies, i.e.,
+///     Gamma(a + 1/2) * Gamma(b + 1/2) / (pi * Gamma(a + b + 1))
+fn log_kt_block_prob(zeroes: u64, ones: u64) -> f64 {
+  ln_gamma(zeroes as f64 + 0.5) + ln_gamma(ones as f64 + 0.5) -
+      ln_gamma((zeroes + ones) as f64 + 1.0) - f64::consts::PI.ln()
+}
+
+/// Returns the natural log of the gamma function, computed using the Lanczos
+/// approximation. Only valid for x >= 0.5.
+fn ln_gamma(x: f64) -> f64 {
+  const G: f64 = 7.0;
+  const COEFFICIENTS: [f64; 9] = [
+    0.99999999999980993,
+    676.5203681218851,
+    -1259.1392167224028,
+    771.32342877765313,
+    -176.61502916214059,
+    12.507343278686905,
+    -0.13857109526572012,
+    9.9843695780195716e-6,
+    1.5056327351493116e-7,
+  ];
+  assert!(x >= 0.5);
+
+  let x = x - 1.0;
+  let mut sum = COEFFICIENTS[0];
+  for i in 1..COEFFICIENTS.len() {
+    sum = sum + COEFFICIENTS[i] / (x + i as f64);
+  }
+  let t = x + G + 0.5;
+  return 0.5 * (2.0 * f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln();
+}
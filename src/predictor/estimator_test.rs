@@ -0,0 +1,99 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use bitstring::Bit;
+use predictor::estimator::{Estimator, EstimatorType};
+use predictor::test_util::assert_almost_eq;
+
+const EPS: f64 = 0.0000001;
+
+#[test]
+fn krichevsky_trofimov() {
+  let estimator = EstimatorType::KrichevskyTrofimov.create();
+  assert_almost_eq(0.5, prob(&*estimator, 0, 0, Bit::One), EPS);
+  assert_almost_eq(3.5 / 5.0, prob(&*estimator, 1, 3, Bit::One), EPS);
+}
+
+#[test]
+fn laplace() {
+  let estimator = EstimatorType::Laplace.create();
+  assert_almost_eq(0.5, prob(&*estimator, 0, 0, Bit::Zero), EPS);
+  assert_almost_eq(2.0 / 6.0, prob(&*estimator, 1, 3, Bit::Zero), EPS);
+}
+
+#[test]
+fn dirichlet() {
+  let estimator = EstimatorType::Dirichlet(0.1).create();
+  assert_almost_eq(3.1 / 4.2, prob(&*estimator, 1, 3, Bit::One), EPS);
+
+  // Dirichlet with alpha = 0.5 is Krichevsky-Trofimov.
+  let dirichlet = EstimatorType::Dirichlet(0.5).create();
+  let kt = EstimatorType::KrichevskyTrofimov.create();
+  assert_almost_eq(
+      prob(&*kt, 7, 2, Bit::Zero), prob(&*dirichlet, 7, 2, Bit::Zero), EPS);
+}
+
+#[test]
+fn zero_redundancy() {
+  let estimator = EstimatorType::ZeroRedundancy.create();
+  assert_almost_eq(0.5, prob(&*estimator, 0, 0, Bit::One), EPS);
+
+  // P(0) = 1/2 * 1/2 + 1/4 and P(00) = 1/2 * 3/8 + 1/4.
+  assert_almost_eq(
+      (0.5 * 0.375 + 0.25) / 0.5, prob(&*estimator, 1, 0, Bit::Zero), EPS);
+
+  // Deterministic sequences quickly become very likely.
+  assert!(prob(&*estimator, 20, 0, Bit::Zero) > 0.99);
+
+  // Once both bits have been seen, this is Krichevsky-Trofimov.
+  let kt = EstimatorType::KrichevskyTrofimov.create();
+  assert_almost_eq(
+      prob(&*kt, 4, 3, Bit::One), prob(&*estimator, 4, 3, Bit::One), EPS);
+}
+
+#[test]
+fn probabilities_sum_to_one() {
+  let estimators = [
+    EstimatorType::KrichevskyTrofimov,
+    EstimatorType::Laplace,
+    EstimatorType::Dirichlet(0.05),
+    EstimatorType::ZeroRedundancy,
+  ];
+  for estimator_type in estimators.iter() {
+    let estimator = estimator_type.create();
+    for &(zeroes, ones) in [(0, 0), (0, 5), (9, 0), (3, 4), (0, 1000)].iter() {
+      let total = prob(&*estimator, zeroes, ones, Bit::Zero) +
+          prob(&*estimator, zeroes, ones, Bit::One);
+      assert_almost_eq(1.0, total, EPS);
+    }
+  }
+}
+
+#[test]
+#[should_panic]
+fn dirichlet_requires_positive_alpha() {
+  EstimatorType::Dirichlet(0.0).create();
+}
+
+fn prob(estimator: &Estimator, zeroes: u64, ones: u64, bit: Bit) -> f64 {
+  estimator.log_conditional_prob(zeroes, ones, bit).exp2()
+}
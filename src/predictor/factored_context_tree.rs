@@ -0,0 +1,203 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::io::{self, Read, Write};
+
+use bitstring::{Bit, Bitstring};
+use predictor::context_tree::ContextTree;
+use predictor::estimator::EstimatorType;
+use predictor::Predictor;
+use random::Random;
+use serialization;
+
+/// Identifies the serialized format of a factored context tree.
+pub const MAGIC: &[u8; 4] = b"FCTW";
+const VERSION: u32 = 1;
+
+/// Predictor which uses a separate context tree for every bit position
+/// within a percept. All trees condition on the same history, but each tree
+/// only learns from (and predicts) the bits at its own position. This allows
+/// every bit of a structured percept to be modeled independently, e.g., the
+/// reward bits do not share statistics with the observation bits.
+///
+/// The position of a bit is determined by counting the bits added through
+/// update. Bits added through update_history (e.g., actions) do not count.
+pub struct FactoredContextTree {
+  trees: Vec<ContextTree>,
+
+  /// For every bit in the history, whether or not it was added through
+  /// update. Needed in order to track positions correctly when reverting.
+  updated: Vec<bool>,
+
+  /// The number of bits in the history which were added through update.
+  num_updated: usize,
+}
+
+impl FactoredContextTree {
+  /// Creates a predictor for percepts of the supplied size, using one context
+  /// tree of the supplied depth per bit.
+  pub fn create(
+      percept_bits: usize, depth: usize, estimator: EstimatorType) -> Self {
+    assert!(percept_bits > 0);
+    FactoredContextTree {
+      trees: (0..percept_bits)
+          .map(|_| ContextTree::create(depth, estimator))
+          .collect(),
+      updated: Vec::new(),
+      num_updated: 0,
+    }
+  }
+
+  /// Returns the total number of nodes across all trees.
+  pub fn size(&self) -> usize {
+    self.trees.iter().map(|tree| tree.size()).sum()
+  }
+
+  /// Reads a predictor previously written by save().
+  pub fn load(reader: &mut Read) -> io::Result<FactoredContextTree> {
+    serialization::read_header(reader, MAGIC, VERSION)?;
+    let updated: Vec<bool> = serialization::read_bitstring(reader)?
+        .iter()
+        .map(|bit| bit == Bit::One)
+        .collect();
+    let num_trees = serialization::read_u64(reader)?;
+    if num_trees == 0 {
+      return Err(serialization::invalid_data("Expected at least one tree"));
+    }
+
+    let mut trees = Vec::new();
+    for _ in 0..num_trees {
+      let tree = ContextTree::load(reader)?;
+      if tree.history_size() != updated.len() {
+        return Err(serialization::invalid_data(
+            "Tree history does not match the predictor"));
+      }
+      trees.push(tree);
+    }
+
+    let num_updated = updated.iter().filter(|&&updated| updated).count();
+    return Ok(FactoredContextTree {
+      trees: trees,
+      updated: updated,
+      num_updated: num_updated,
+    });
+  }
+
+  /// Returns the index of the tree responsible for the next updated bit.
+  fn current_index(&self) -> usize {
+    self.num_updated % self.trees.len()
+  }
+
+  /// Informs all trees except the one at the supplied index of a new bit.
+  fn update_others(&mut self, index: usize, bit: &Bitstring) {
+    for (i, tree) in self.trees.iter_mut().enumerate() {
+      if i != index {
+        tree.update_history(bit);
+      }
+    }
+    self.updated.push(true);
+    self.num_updated = self.num_updated + 1;
+  }
+
+  /// Adds a single bit to the history, updating the responsible tree.
+  fn update_bit(&mut self, bit: Bit) {
+    let index = self.current_index();
+    let bits = single_bit(bit);
+    self.trees[index].update(&bits);
+    self.update_others(index, &bits);
+  }
+}
+
+impl Predictor for FactoredContextTree {
+  fn history_size(&self) -> usize {
+    self.updated.len()
+  }
+
+  fn revert_to_history_size(&mut self, target_size: usize) {
+    assert!(self.history_size() >= target_size);
+    for tree in self.trees.iter_mut() {
+      tree.revert_to_history_size(target_size);
+    }
+    while self.history_size() > target_size {
+      if self.updated.pop().unwrap() {
+        self.num_updated = self.num_updated - 1;
+      }
+    }
+  }
+
+  fn update(&mut self, bitstring: &Bitstring) {
+    for bit in bitstring.iter() {
+      self.update_bit(bit);
+    }
+  }
+
+  fn update_history(&mut self, bitstring: &Bitstring) {
+    for tree in self.trees.iter_mut() {
+      tree.update_history(bitstring);
+    }
+    for _ in 0..bitstring.len() {
+      self.updated.push(false);
+    }
+  }
+
+  fn predict(&mut self, bits: &Bitstring) -> f64 {
+    // The probability of the sequence is the product of the conditional
+    // probabilities of its bits, each computed by the responsible tree.
+    let initial_size = self.history_size();
+    let mut result = 1.0;
+    for bit in bits.iter() {
+      let index = self.current_index();
+      result = result * self.trees[index].predict(&single_bit(bit));
+      self.update_bit(bit);
+    }
+    self.revert_to_history_size(initial_size);
+    return result;
+  }
+
+  /// Writes the bit positions followed by every tree in the format of
+  /// ContextTree::save.
+  fn save(&self, writer: &mut Write) -> io::Result<()> {
+    serialization::write_header(writer, MAGIC, VERSION)?;
+    let updated: Bitstring = self.updated.iter()
+        .map(|&updated| if updated { Bit::One } else { Bit::Zero })
+        .collect();
+    serialization::write_bitstring(writer, &updated)?;
+    serialization::write_u64(writer, self.trees.len() as u64)?;
+    for tree in self.trees.iter() {
+      tree.save(writer)?;
+    }
+    return Ok(());
+  }
+
+  fn sample_and_update_bit(&mut self, random: &mut Random) -> Bit {
+    let index = self.current_index();
+    let bit = self.trees[index].sample_and_update_bit(random);
+    self.update_others(index, &single_bit(bit));
+    return bit;
+  }
+}
+
+fn single_bit(bit: Bit) -> Bitstring {
+  let mut result = Bitstring::new();
+  result.push(bit);
+  return result;
+}
@@ -0,0 +1,141 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::io::Cursor;
+
+use bitstring::Bitstring;
+use predictor;
+use predictor::context_tree::ContextTree;
+use predictor::factored_context_tree::FactoredContextTree;
+use predictor::estimator::EstimatorType;
+use predictor::Predictor;
+use predictor::test_util::assert_almost_eq;
+use random::RandomImpl;
+
+const EPS: f64 = 0.0000001;
+const KT: EstimatorType = EstimatorType::KrichevskyTrofimov;
+
+#[test]
+fn size() {
+  let mut tree = FactoredContextTree::create(3, 2, KT);
+  assert_eq!(3, tree.size());  // One root per tree.
+
+  // Only the tree responsible for the third bit learns from it.
+  tree.update(&Bitstring::create_from_string("011"));
+  assert_eq!(5, tree.size());
+}
+
+#[test]
+fn single_tree_matches_context_tree() {
+  let history = Bitstring::create_from_string("0110100111010");
+  let bits = Bitstring::create_from_string("0110");
+
+  let mut factored = FactoredContextTree::create(1, 3, KT);
+  factored.update(&history);
+  let mut tree = ContextTree::create(3, KT);
+  tree.update(&history);
+
+  assert_almost_eq(tree.predict(&bits), factored.predict(&bits), EPS);
+}
+
+#[test]
+fn learns_per_position() {
+  // Percepts of two bits in which the first bit is always 0 and the second
+  // bit is always 1. Every percept is preceded by an action bit.
+  let mut tree = FactoredContextTree::create(2, 1, KT);
+  for i in 0..30 {
+    let action = if i % 2 == 0 { "0" } else { "1" };
+    tree.update_history(&Bitstring::create_from_string(action));
+    tree.update(&Bitstring::create_from_string("01"));
+  }
+
+  tree.update_history(&Bitstring::create_from_string("1"));
+  assert!(tree.predict(&Bitstring::create_from_string("01")) > 0.9);
+  assert!(tree.predict(&Bitstring::create_from_string("10")) < 0.01);
+}
+
+#[test]
+fn revert_restores_state() {
+  let mut tree = FactoredContextTree::create(3, 2, KT);
+  tree.update(&Bitstring::create_from_string("01101"));
+  tree.update_history(&Bitstring::create_from_string("1"));
+  tree.update(&Bitstring::create_from_string("0"));
+  let initial_size = tree.history_size();
+  let bits = Bitstring::create_from_string("011");
+  let initial_prob = tree.predict(&bits);
+
+  tree.update(&Bitstring::create_from_string("11"));
+  tree.update_history(&Bitstring::create_from_string("01"));
+  tree.update(&Bitstring::create_from_string("1001"));
+  tree.revert_to_history_size(initial_size);
+
+  assert_eq!(initial_size, tree.history_size());
+  assert_almost_eq(initial_prob, tree.predict(&bits), EPS);
+}
+
+#[test]
+fn sample_and_update() {
+  let mut tree = FactoredContextTree::create(2, 2, KT);
+  tree.update(&Bitstring::create_from_string("011010"));
+
+  let mut random = RandomImpl::create(1234);
+  let bits = tree.sample_and_update_bits(&mut random, 5);
+  assert_eq!(5, bits.len());
+  assert_eq!(11, tree.history_size());
+}
+
+#[test]
+fn save_and_load() {
+  let mut tree = FactoredContextTree::create(3, 2, KT);
+  tree.update(&Bitstring::create_from_string("011010"));
+  tree.update_history(&Bitstring::create_from_string("1"));
+  tree.update(&Bitstring::create_from_string("1"));
+
+  let mut bytes = Vec::new();
+  tree.save(&mut bytes).unwrap();
+  let mut loaded = FactoredContextTree::load(&mut Cursor::new(bytes)).unwrap();
+  assert_eq!(tree.size(), loaded.size());
+
+  // The next bit is handled by the second tree in both predictors.
+  let next = Bitstring::create_from_string("01");
+  assert_eq!(tree.predict(&next), loaded.predict(&next));
+}
+
+#[test]
+fn load_any_predictor() {
+  let mut tree = FactoredContextTree::create(2, 1, KT);
+  tree.update(&Bitstring::create_from_string("0110"));
+  let mut bytes = Vec::new();
+  tree.save(&mut bytes).unwrap();
+  let mut loaded = predictor::load(&mut Cursor::new(bytes)).unwrap();
+  let next = Bitstring::create_from_string("1");
+  assert_eq!(tree.predict(&next), loaded.predict(&next));
+
+  let mut context_tree = ContextTree::create(2, KT);
+  context_tree.update(&Bitstring::create_from_string("0110"));
+  let mut bytes = Vec::new();
+  context_tree.save(&mut bytes).unwrap();
+  let mut loaded = predictor::load(&mut Cursor::new(bytes)).unwrap();
+  assert_eq!(context_tree.predict(&next), loaded.predict(&next));
+
+  assert!(predictor::load(&mut Cursor::new(b"NOPE".to_vec())).is_err());
+}
@@ -22,11 +22,16 @@
 
 
 pub mod context_tree;
+pub mod estimator;
+pub mod factored_context_tree;
 
 mod predictor;
-pub use predictor::predictor::Predictor;
+pub use predictor::predictor::{load, Predictor};
 
 // Unit test modules.
 
 #[cfg(test)] pub mod context_tree_test;
+#[cfg(test)] pub mod estimator_test;
+#[cfg(test)] pub mod factored_context_tree_test;
+#[cfg(test)] mod test_util;
 
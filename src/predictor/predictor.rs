@@ -20,7 +20,12 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use bitstring::Bitstring;
+use std::io::{self, Read, Write};
+
+use bitstring::{Bit, Bitstring};
+use predictor::context_tree::{self, ContextTree};
+use predictor::factored_context_tree::{self, FactoredContextTree};
+use random::Random;
 
 /// An object capable of predicting observations and rewards based on
 /// experience. Predictors have an abstract notion of history which
@@ -32,6 +37,12 @@ pub trait Predictor {
   /// Appends the provided bit string to the tracked history.
   fn update(&mut self, bitstring: &Bitstring);
 
+  /// Appends the provided bit string to the tracked history without learning
+  /// from it, i.e., the bits become part of the context for future
+  /// predictions, but are never themselves predicted. Used for bits which
+  /// are chosen rather than observed, such as the agent's actions.
+  fn update_history(&mut self, bitstring: &Bitstring);
+
   /// Reverts the context tree to a previous state by undoing update
   /// operations. The specified size must be at most the current size.
   fn revert_to_history_size(&mut self, target_size: usize);
@@ -39,5 +50,69 @@ pub trait Predictor {
   /// Returns the probability, given the current history, that "bits" are the
   /// next observed symbols.
   fn predict(&mut self, bits: &Bitstring) -> f64;
+
+  /// Writes this predictor in a format which can be restored using load.
+  /// Predictors which cannot be restored return an error.
+  fn save(&self, writer: &mut Write) -> io::Result<()> {
+    #![allow(unused_variables)]
+    Err(io::Error::new(io::ErrorKind::Other, "not serializable"))
+  }
+
+  /// Samples the next bit from the distribution over continuations of the
+  /// current history and appends it to the history.
+  fn sample_and_update_bit(&mut self, random: &mut Random) -> Bit {
+    let prob_one = self.predict(&Bitstring::create_from_string("1"));
+    let bit = if random.next_f64() < prob_one { Bit::One } else { Bit::Zero };
+    let mut bits = Bitstring::new();
+    bits.push(bit);
+    self.update(&bits);
+    return bit;
+  }
+
+  /// Samples the next bit given the current history. Leaves the history
+  /// unchanged.
+  fn sample_bit(&mut self, random: &mut Random) -> Bit {
+    let history_size = self.history_size();
+    let bit = self.sample_and_update_bit(random);
+    self.revert_to_history_size(history_size);
+    return bit;
+  }
+
+  /// Samples the next num_bits bits one by one, appending each sampled bit to
+  /// the history before sampling the next one.
+  fn sample_and_update_bits(
+      &mut self, random: &mut Random, num_bits: usize) -> Bitstring {
+    let mut result = Bitstring::new();
+    for _ in 0..num_bits {
+      result.push(self.sample_and_update_bit(random));
+    }
+    return result;
+  }
+
+  /// Samples the next num_bits bits given the current history. Leaves the
+  /// history unchanged.
+  fn sample_bits(&mut self, random: &mut Random, num_bits: usize) -> Bitstring {
+    let history_size = self.history_size();
+    let result = self.sample_and_update_bits(random, num_bits);
+    self.revert_to_history_size(history_size);
+    return result;
+  }
 }
 
+/// Restores a predictor written by Predictor::save. The type of predictor is
+/// determined by the magic tag at the start of the data.
+pub fn load(reader: &mut Read) -> io::Result<Box<Predictor>> {
+  let mut magic = [0; 4];
+  reader.read_exact(&mut magic)?;
+
+  // Hand the tag back to the predictor, which checks it again.
+  let mut reader = (&magic[..]).chain(reader);
+  if &magic == context_tree::MAGIC {
+    Ok(Box::new(ContextTree::load(&mut reader)?))
+  } else if &magic == factored_context_tree::MAGIC {
+    Ok(Box::new(FactoredContextTree::load(&mut reader)?))
+  } else {
+    Err(io::Error::new(io::ErrorKind::InvalidData,
+        format!("Unknown predictor type {:?}", magic)))
+  }
+}
@@ -20,27 +20,62 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use std::io::{self, Read, Write};
+
 use rand::{Rng, SeedableRng, StdRng};
 
+use serialization;
+
 /// Basic random number generator (not necessarily cryptographically secure).
 pub trait Random {
   /// Returns a random number in the range [0, limit - 1].
   fn next_modulo(&mut self, limit: u64) -> u64;
+
+  /// Returns a random number in the range [0, 1).
+  fn next_f64(&mut self) -> f64 {
+    const RESOLUTION: u64 = 1 << 53;
+    return self.next_modulo(RESOLUTION) as f64 / RESOLUTION as f64;
+  }
 }
 
 /// Default implementation of the Random trait.
 pub struct RandomImpl {
   generator: StdRng,
+
+  /// The generator's position is fully determined by its seed and the number
+  /// of values drawn from it, which allows saving and restoring it.
+  seed: usize,
+  num_draws: u64,
 }
 
 impl RandomImpl {
   pub fn create(seed: usize) -> Self {
-    let seed_slice: &[_] = &[seed as usize];
+    let seed_slice: &[_] = &[seed];
     return RandomImpl {
       generator: SeedableRng::from_seed(seed_slice),
+      seed: seed,
+      num_draws: 0,
     };
   }
 
+  /// Writes the seed and the current position of this generator.
+  pub fn save(&self, writer: &mut Write) -> io::Result<()> {
+    serialization::write_u64(writer, self.seed as u64)?;
+    serialization::write_u64(writer, self.num_draws)
+  }
+
+  /// Restores a generator written by save(). The generator is advanced to
+  /// the saved position by replaying all draws.
+  pub fn load(reader: &mut Read) -> io::Result<RandomImpl> {
+    let seed = serialization::read_u64(reader)? as usize;
+    let num_draws = serialization::read_u64(reader)?;
+    let mut result = RandomImpl::create(seed);
+    for _ in 0..num_draws {
+      result.next();
+    }
+    return Ok(result);
+  }
+
   /// Returns a new random number generator seeded with the
   /// next random number produced by this generator.
   pub fn new_child(&mut self) -> Self {
@@ -49,6 +84,7 @@ impl RandomImpl {
 
   /// Returns a random number.
   fn next(&mut self) -> u64 {
+    self.num_draws = self.num_draws + 1;
     self.generator.gen::<u64>()
   }
 }
@@ -57,4 +93,10 @@ impl Random for RandomImpl {
   fn next_modulo(&mut self, limit: u64) -> u64 {
     return self.next() % limit;
   }
+
+  fn next_f64(&mut self) -> f64 {
+    // Consumes exactly one u64 from the generator, just like next().
+    self.num_draws = self.num_draws + 1;
+    self.generator.gen::<f64>()
+  }
 }
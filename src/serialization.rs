@@ -0,0 +1,120 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Helpers for reading and writing the binary formats used to persist models
+//! and checkpoints. All integers are stored in little-endian byte order and
+//! every format starts with a magic tag followed by a version number.
+
+use std::io::{self, Read, Write};
+
+use bitstring::Bitstring;
+
+pub fn write_header(
+    writer: &mut Write, magic: &[u8; 4], version: u32) -> io::Result<()> {
+  writer.write_all(magic)?;
+  write_u32(writer, version)
+}
+
+/// Reads a header and checks that it matches the supplied magic tag. Returns
+/// the version, which must be in [1, max_version].
+pub fn read_header(
+    reader: &mut Read, magic: &[u8; 4], max_version: u32) -> io::Result<u32> {
+  let mut actual = [0; 4];
+  reader.read_exact(&mut actual)?;
+  if actual != *magic {
+    return Err(invalid_data(&format!(
+        "Expected magic {:?} but got {:?}", magic, actual)));
+  }
+
+  let version = read_u32(reader)?;
+  if version == 0 || version > max_version {
+    return Err(invalid_data(&format!(
+        "Unsupported version {}, expected at most {}", version, max_version)));
+  }
+  return Ok(version);
+}
+
+pub fn write_u8(writer: &mut Write, value: u8) -> io::Result<()> {
+  writer.write_all(&[value])
+}
+
+pub fn read_u8(reader: &mut Read) -> io::Result<u8> {
+  let mut bytes = [0; 1];
+  reader.read_exact(&mut bytes)?;
+  return Ok(bytes[0]);
+}
+
+pub fn write_u32(writer: &mut Write, value: u32) -> io::Result<()> {
+  writer.write_all(&value.to_le_bytes())
+}
+
+pub fn read_u32(reader: &mut Read) -> io::Result<u32> {
+  let mut bytes = [0; 4];
+  reader.read_exact(&mut bytes)?;
+  return Ok(u32::from_le_bytes(bytes));
+}
+
+pub fn write_u64(writer: &mut Write, value: u64) -> io::Result<()> {
+  writer.write_all(&value.to_le_bytes())
+}
+
+pub fn read_u64(reader: &mut Read) -> io::Result<u64> {
+  let mut bytes = [0; 8];
+  reader.read_exact(&mut bytes)?;
+  return Ok(u64::from_le_bytes(bytes));
+}
+
+/// Stores the exact bit pattern of the value, so reading it back yields the
+/// identical value.
+pub fn write_f64(writer: &mut Write, value: f64) -> io::Result<()> {
+  write_u64(writer, value.to_bits())
+}
+
+pub fn read_f64(reader: &mut Read) -> io::Result<f64> {
+  Ok(f64::from_bits(read_u64(reader)?))
+}
+
+pub fn write_bitstring(writer: &mut Write, bits: &Bitstring) -> io::Result<()> {
+  write_u64(writer, bits.len() as u64)?;
+  writer.write_all(&bits.to_bytes())
+}
+
+/// Reads a bitstring written by write_bitstring. Only the bytes which are
+/// actually present are allocated, so a corrupt length results in an error.
+pub fn read_bitstring(reader: &mut Read) -> io::Result<Bitstring> {
+  let len = read_u64(reader)?;
+  let mut bytes = Vec::new();
+  reader.take(len.div_ceil(8)).read_to_end(&mut bytes)?;
+  if (bytes.len() as u64) < len.div_ceil(8) {
+    return Err(io::Error::new(
+        io::ErrorKind::UnexpectedEof, "Bitstring is truncated"));
+  }
+
+  let mut result = Bitstring::create_from_bytes(&bytes);
+  result.truncate(len as usize);
+  return Ok(result);
+}
+
+/// Returns an error indicating that the data being read is malformed.
+pub fn invalid_data(message: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
@@ -0,0 +1,57 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 dinowernli
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::io::{Cursor, ErrorKind};
+
+use bitstring::Bitstring;
+use serialization;
+
+#[test]
+fn bitstring_round_trip() {
+  for value in ["", "1", "01101", "110010101100101011001010110010101"].iter() {
+    let bits = Bitstring::create_from_string(value);
+    let mut bytes = Vec::new();
+    serialization::write_bitstring(&mut bytes, &bits).unwrap();
+    let loaded =
+        serialization::read_bitstring(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(bits, loaded);
+  }
+}
+
+#[test]
+fn bitstring_huge_length() {
+  let mut bytes = Vec::new();
+  serialization::write_u64(&mut bytes, u64::max_value()).unwrap();
+  bytes.extend_from_slice(&[0xff; 4]);
+  let error =
+      serialization::read_bitstring(&mut Cursor::new(bytes)).err().unwrap();
+  assert_eq!(ErrorKind::UnexpectedEof, error.kind());
+}
+
+#[test]
+fn bitstring_truncated() {
+  let mut bytes = Vec::new();
+  serialization::write_bitstring(
+      &mut bytes, &Bitstring::create_from_string("110010101")).unwrap();
+  bytes.pop();
+  assert!(serialization::read_bitstring(&mut Cursor::new(bytes)).is_err());
+}
@@ -44,8 +44,11 @@ impl Div<f64> for Reward {
   }
 }
 
+/// Observations are non-negative and encoded in at most 31 bits. Some
+/// environments pack a lot of state into an observation, e.g., Tic-Tac-Toe
+/// uses 2 bits for each of its 9 cells, which does not fit into 16 bits.
 #[derive(Copy, Clone, Debug)]
-pub struct Observation(pub i16);
+pub struct Observation(pub i32);
 
 #[derive(Copy, Clone, Debug)]
 pub struct Action(pub i16);